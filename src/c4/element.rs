@@ -0,0 +1,174 @@
+use std::fmt;
+
+use crate::render::EscapeStyle;
+use crate::utils::escape_quoted;
+
+#[derive(Debug, PartialEq)]
+pub enum ElementKind {
+    Person,
+    System,
+    Container,
+}
+
+impl fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind_str = match self {
+            ElementKind::Person => "Person",
+            ElementKind::System => "System",
+            ElementKind::Container => "Container",
+        };
+        write!(f, "{kind_str}")
+    }
+}
+
+/// Represents a person, system, or container in a C4 diagram.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::c4::{Element, ElementKind};
+///
+/// let customer = Element::new(ElementKind::Person, "customer", "Customer")
+///     .with_description("A customer of the bank");
+/// ```
+#[must_use]
+pub struct Element {
+    pub kind: ElementKind,
+    /// The alias is used to reference the element in relationships.
+    pub alias: String,
+    /// The label is the name displayed in the rendered version of the diagram.
+    pub label: String,
+    /// The technology used to build the element, only rendered for containers.
+    pub technology: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Element {
+    pub fn new(kind: ElementKind, alias: &str, label: &str) -> Self {
+        Element {
+            kind,
+            alias: alias.to_string(),
+            label: label.to_string(),
+            technology: None,
+            description: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn with_technology(mut self, technology: &str) -> Self {
+        self.technology = Some(technology.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // format the kind, alias, and label, escaping quotes in each quoted string
+        let quoted = |text: &str| escape_quoted(text, EscapeStyle::default());
+        let mut out_str = format!("{}({}, \"{}\"", self.kind, self.alias, quoted(&self.label));
+        // containers accept a technology before the description
+        if self.kind == ElementKind::Container {
+            match (self.technology.as_deref(), self.description.as_deref()) {
+                (Some(technology), _) => out_str += &format!(", \"{}\"", quoted(technology)),
+                (None, Some(_)) => out_str += ", \"\"",
+                (None, None) => (),
+            }
+        }
+        // format the description if it's populated
+        if let Some(description) = self.description.as_deref() {
+            out_str += &format!(", \"{}\"", quoted(description));
+        }
+        // append the closing parenthesis
+        out_str += ")";
+        write!(f, "{out_str}")
+    }
+}
+
+// ==================================================================
+// Element tests
+// ==================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALIAS: &str = "customer";
+    const LABEL: &str = "Customer";
+    const DESCRIPTION: &str = "A customer of the bank";
+
+    #[test]
+    fn create_element_without_description() {
+        // act
+        let element = Element::new(ElementKind::Person, ALIAS, LABEL);
+        // assert
+        assert_eq!(element.kind, ElementKind::Person);
+        assert_eq!(element.alias, ALIAS);
+        assert_eq!(element.label, LABEL);
+        assert_eq!(element.description, None);
+    }
+
+    #[test]
+    fn display_person_with_description() {
+        // arrange
+        let wanted = "Person(customer, \"Customer\", \"A customer of the bank\")";
+        // act
+        let got = Element::new(ElementKind::Person, ALIAS, LABEL)
+            .with_description(DESCRIPTION)
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_system_without_description() {
+        // arrange
+        let wanted = "System(banking, \"Internet Banking\")";
+        // act
+        let got = Element::new(ElementKind::System, "banking", "Internet Banking").to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_container_with_description_but_no_technology() {
+        // arrange
+        let wanted = "Container(api, \"API\", \"\", \"Serves the banking data\")";
+        // act
+        let got = Element::new(ElementKind::Container, "api", "API")
+            .with_description("Serves the banking data")
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_container_with_technology_and_description() {
+        // arrange
+        let wanted = "Container(api, \"API\", \"Rust\", \"Serves the banking data\")";
+        // act
+        let got = Element::new(ElementKind::Container, "api", "API")
+            .with_technology("Rust")
+            .with_description("Serves the banking data")
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_container_with_quotes_escaped() {
+        // arrange
+        let wanted =
+            r#"Container(api, "The #quot;API#quot;", "Rust #35;1", "Serves #quot;data#quot;")"#;
+        // act
+        let got = Element::new(ElementKind::Container, "api", "The \"API\"")
+            .with_technology("Rust #1")
+            .with_description("Serves \"data\"")
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+
+pub mod element;
+pub mod relationship;
+
+use crate::{utils, MormaidError};
+pub use element::{Element, ElementKind};
+pub use relationship::Rel;
+
+// ================================================================
+// C4Context struct and implementation
+// ================================================================
+
+/// Represents a mermaid `C4Context` diagram.
+///
+/// Elements are stored in the order they're added, because the order of
+/// declarations affects the layout of the rendered C4 diagram.
+#[must_use]
+#[derive(Default)]
+pub struct C4Context {
+    pub title: Option<String>,
+    pub elements: Vec<Element>,
+    pub relationships: Vec<Rel>,
+}
+impl C4Context {
+    pub fn new() -> Self {
+        C4Context {
+            title: None,
+            elements: Vec::new(),
+            relationships: Vec::new(),
+        }
+    }
+
+    /// Add a title to the diagram on creation by chaining with [`C4Context::new()`].
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+}
+
+// implement the Display trait
+impl fmt::Display for C4Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // initialize the C4Context diagram
+        let mut out_str = "C4Context".to_string();
+
+        // append the title if the diagram has one
+        if let Some(title) = self.title.as_deref() {
+            out_str += &format!("\n    title {title}");
+        }
+
+        // append elements if the diagram has them
+        if !self.elements.is_empty() {
+            out_str = utils::append_items(out_str, &self.elements, "Elements", 4);
+        }
+
+        // append relationships if the diagram has them
+        if !self.relationships.is_empty() {
+            out_str = utils::append_items(out_str, &self.relationships, "Relationships", 4);
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+// ===============================================
+// Implement C4Context methods to manage elements
+// ===============================================
+impl C4Context {
+    /// Add an element to `C4Context.elements`.
+    pub fn add_element(&mut self, element: Element) {
+        self.elements.push(element);
+    }
+
+    /// Add an element to the diagram on creation by chaining with [`C4Context::new()`].
+    pub fn with_element(mut self, element: Element) -> Self {
+        self.add_element(element);
+        self
+    }
+
+    /// Add a person to the diagram on creation by chaining with [`C4Context::new()`].
+    pub fn with_person(self, alias: &str, label: &str, description: &str) -> Self {
+        self.with_element(
+            Element::new(ElementKind::Person, alias, label).with_description(description),
+        )
+    }
+
+    /// Add a system to the diagram on creation by chaining with [`C4Context::new()`].
+    pub fn with_system(self, alias: &str, label: &str, description: &str) -> Self {
+        self.with_element(
+            Element::new(ElementKind::System, alias, label).with_description(description),
+        )
+    }
+
+    /// Add a container to the diagram on creation by chaining with [`C4Context::new()`].
+    pub fn with_container(self, alias: &str, label: &str, description: &str) -> Self {
+        self.with_element(
+            Element::new(ElementKind::Container, alias, label).with_description(description),
+        )
+    }
+
+    /// Try to find an element in the diagram using its alias.
+    #[must_use]
+    pub fn get_element_by_alias(&self, alias: &str) -> Option<&Element> {
+        self.elements.iter().find(|element| element.alias == alias)
+    }
+}
+
+// ====================================================
+// Implement C4Context methods to manage relationships
+// ====================================================
+impl C4Context {
+    /// Add a relationship to `C4Context.relationships`.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingElement`] if either end of the relationship
+    /// references an element that hasn't been added to the diagram.
+    pub fn add_rel(&mut self, rel: Rel) -> Result<(), MormaidError> {
+        // Ensure that both ends of the relationship exist in the diagram
+        for alias in [&rel.from, &rel.to] {
+            if self.get_element_by_alias(alias).is_none() {
+                return Err(MormaidError::MissingElement(alias.clone()));
+            }
+        }
+        // Then add the relationship to the diagram
+        self.relationships.push(rel);
+        Ok(())
+    }
+
+    /// Add a relationship to the diagram on creation by chaining with [`C4Context::new()`].
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingElement`] if either end of the relationship
+    /// references an element that hasn't been added to the diagram.
+    pub fn with_rel(mut self, rel: Rel) -> Result<Self, MormaidError> {
+        self.add_rel(rel)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const PERSON: &str = "customer";
+    const SYSTEM: &str = "banking";
+
+    fn person_and_system() -> C4Context {
+        C4Context::new()
+            .with_person(PERSON, "Customer", "A customer of the bank")
+            .with_system(SYSTEM, "Internet Banking", "Allows customers to pay bills")
+    }
+
+    #[test]
+    fn create_diagram_with_elements() {
+        // act
+        let diagram = person_and_system();
+        // assert
+        assert_eq!(diagram.elements.len(), 2);
+        let person = diagram
+            .get_element_by_alias(PERSON)
+            .expect("Expected element but got None");
+        assert_eq!(person.kind, ElementKind::Person);
+    }
+
+    #[test]
+    fn add_rel_between_declared_elements() {
+        // act
+        let diagram = person_and_system()
+            .with_rel(Rel::new(PERSON, SYSTEM, "Uses"))
+            .expect("Expected relationship to be added");
+        // assert
+        assert_eq!(diagram.relationships.len(), 1);
+    }
+
+    #[test]
+    fn add_rel_to_undeclared_element_should_error() {
+        // arrange
+        let mut diagram = person_and_system();
+        // act
+        let got = diagram.add_rel(Rel::new(PERSON, "Fake", "Uses"));
+        // assert
+        assert_eq!(got, Err(MormaidError::MissingElement("Fake".to_string())));
+        assert!(diagram.relationships.is_empty());
+    }
+
+    #[test]
+    fn display_empty_diagram() {
+        // arrange
+        let wanted = "C4Context";
+        // act
+        let got = C4Context::new().to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_person_to_system_relationship() {
+        // arrange
+        let wanted = concat!(
+            "C4Context\n",
+            "    title Banking\n",
+            "    %% Elements start\n",
+            "    Person(customer, \"Customer\", \"A customer of the bank\")\n",
+            "    System(banking, \"Internet Banking\", \"Allows customers to pay bills\")\n",
+            "    %% Elements end\n",
+            "    %% Relationships start\n",
+            "    Rel(customer, banking, \"Uses\")\n",
+            "    %% Relationships end",
+        );
+        // act
+        let got = person_and_system()
+            .with_title("Banking")
+            .with_rel(Rel::new(PERSON, SYSTEM, "Uses"))
+            .expect("Expected relationship to be added")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use crate::render::EscapeStyle;
+use crate::utils::escape_quoted;
+
+/// Represents a relationship between two elements in a C4 diagram.
+#[must_use]
+pub struct Rel {
+    pub from: String,
+    pub to: String,
+    pub label: String,
+    pub technology: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Rel {
+    // create a new relationship
+    pub fn new(from: &str, to: &str, label: &str) -> Self {
+        Rel {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: label.to_string(),
+            technology: None,
+            description: None,
+        }
+    }
+
+    pub fn with_technology(mut self, technology: &str) -> Self {
+        self.technology = Some(technology.to_string());
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Rel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // format the source, target, and label, escaping quotes in each quoted string
+        let quoted = |text: &str| escape_quoted(text, EscapeStyle::default());
+        let mut out_str = format!(
+            "Rel({}, {}, \"{}\"",
+            self.from,
+            self.to,
+            quoted(&self.label)
+        );
+        // the technology must be populated to pass a description
+        match (self.technology.as_deref(), self.description.as_deref()) {
+            (Some(technology), _) => out_str += &format!(", \"{}\"", quoted(technology)),
+            (None, Some(_)) => out_str += ", \"\"",
+            (None, None) => (),
+        }
+        // format the description if it's populated
+        if let Some(description) = self.description.as_deref() {
+            out_str += &format!(", \"{}\"", quoted(description));
+        }
+        // append the closing parenthesis
+        out_str += ")";
+        write!(f, "{out_str}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: &str = "customer";
+    const TO: &str = "banking";
+    const LABEL: &str = "Uses";
+
+    #[test]
+    fn create_rel() {
+        // act
+        let got = Rel::new(FROM, TO, LABEL);
+        // assert
+        assert_eq!(got.from, FROM);
+        assert_eq!(got.to, TO);
+        assert_eq!(got.label, LABEL);
+    }
+
+    #[test]
+    fn display_rel_with_label_only() {
+        // arrange
+        let wanted = "Rel(customer, banking, \"Uses\")";
+        // act
+        let got = Rel::new(FROM, TO, LABEL).to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_rel_with_description() {
+        // arrange
+        let wanted = "Rel(customer, banking, \"Uses\", \"\", \"Views account balances\")";
+        // act
+        let got = Rel::new(FROM, TO, LABEL)
+            .with_description("Views account balances")
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_rel_with_quotes_escaped() {
+        // arrange
+        let wanted = r#"Rel(customer, banking, "Uses #quot;online#quot;", "", "Views #quot;balances#quot;")"#;
+        // act
+        let got = Rel::new(FROM, TO, "Uses \"online\"")
+            .with_description("Views \"balances\"")
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+}
@@ -0,0 +1,107 @@
+// ==================================================================
+// Render diagrams to images with the mermaid CLI
+// ==================================================================
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::RenderError;
+
+/// The name of the mermaid CLI executable, see <https://github.com/mermaid-js/mermaid-cli>.
+const MMDC: &str = "mmdc";
+
+// Count the renders so concurrent calls in one process use different directories
+static RENDER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// The number of names to try before giving up on creating a temporary directory
+const MAX_TEMP_DIR_ATTEMPTS: usize = 100;
+
+// Render the mermaid source to an image with `mmdc` and return the image bytes
+pub(crate) fn render_image(source: &str, extension: &str) -> Result<Vec<u8>, RenderError> {
+    run(MMDC, source, extension)
+}
+
+// Check that `mmdc` can parse the mermaid source by rendering it and discarding the image
+pub(crate) fn validate(source: &str) -> Result<(), RenderError> {
+    run(MMDC, source, "svg").map(|_| ())
+}
+
+fn run(program: &str, source: &str, extension: &str) -> Result<Vec<u8>, RenderError> {
+    // write the diagram to a temporary file because mmdc reads from a path
+    let dir = create_temp_dir()?;
+    let input = dir.join("diagram.mmd");
+    let output = dir.join(format!("diagram.{extension}"));
+    if let Err(err) = fs::write(&input, source) {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(RenderError::Io(err));
+    }
+
+    let result = Command::new(program)
+        .arg("--input")
+        .arg(&input)
+        .arg("--output")
+        .arg(&output)
+        .output();
+    // read the image before cleaning up, ignoring errors from removing the directory
+    let image = match result {
+        Ok(out) if out.status.success() => fs::read(&output).map_err(RenderError::from),
+        Ok(out) => Err(RenderError::Failed(
+            String::from_utf8_lossy(&out.stderr).trim().to_string(),
+        )),
+        Err(err) if err.kind() == ErrorKind::NotFound => Err(RenderError::MissingCli),
+        Err(err) => Err(RenderError::Io(err)),
+    };
+    let _ = fs::remove_dir_all(&dir);
+    image
+}
+
+// Create a new directory for the files of one render, only readable by the current
+// user on unix. Creating it fails rather than reusing a directory that already
+// exists, e.g. one planted by another user, so each name is tried until one is free.
+fn create_temp_dir() -> Result<PathBuf, RenderError> {
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    for _ in 0..MAX_TEMP_DIR_ATTEMPTS {
+        let count = RENDER_COUNT.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("mormaid-{}-{count}", std::process::id()));
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(RenderError::Io(err)),
+        }
+    }
+    Err(RenderError::Io(io::Error::new(
+        ErrorKind::AlreadyExists,
+        "couldn't create a new temporary directory",
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_program_returns_missing_cli() {
+        // act
+        let got = run("mmdc-not-installed", "erDiagram", "png");
+        // assert
+        assert!(matches!(got, Err(RenderError::MissingCli)));
+    }
+
+    #[test]
+    fn create_temp_dir_returns_a_new_directory_each_call() {
+        // act
+        let first = create_temp_dir().expect("Expected a temporary directory");
+        let second = create_temp_dir().expect("Expected a temporary directory");
+        // assert
+        assert_ne!(first, second);
+        assert!(first.is_dir() && second.is_dir());
+        for dir in [first, second] {
+            fs::remove_dir(dir).expect("Expected an empty directory");
+        }
+    }
+}
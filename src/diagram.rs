@@ -0,0 +1,151 @@
+// ==================================================================
+// Diagram trait and implementations
+// ==================================================================
+
+use std::fmt;
+
+use crate::c4::C4Context;
+use crate::erd::ERD;
+use crate::gitgraph::GitGraph;
+use crate::kanban::Kanban;
+use crate::packet::Packet;
+use crate::quadrant::QuadrantChart;
+use crate::render::RenderOptions;
+use crate::req::RequirementDiagram;
+use crate::xychart::XYChart;
+#[cfg(feature = "cli")]
+use crate::RenderError;
+
+/// Shared behavior of every diagram that can be rendered to mermaid syntax.
+///
+/// The trait is object safe, so diagrams of different types can be collected
+/// as `&dyn Diagram`, e.g. to build a [`crate::report::MarkdownReport`].
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::diagram::Diagram;
+/// # use crate::mormaid::erd::ERD;
+///
+/// let markdown = ERD::new().to_markdown();
+/// assert_eq!(markdown, "```mermaid\nerDiagram\n```");
+/// ```
+pub trait Diagram: fmt::Display {
+    /// Render the diagram to mermaid syntax using the options provided.
+    ///
+    /// Diagrams that don't support every option only apply the line ending.
+    fn render_with(&self, options: &RenderOptions) -> String {
+        options.line_ending.apply(&self.to_string())
+    }
+
+    /// Render the diagram in a fenced `mermaid` code block for a markdown document.
+    fn to_markdown(&self) -> String {
+        format!("```mermaid\n{self}\n```")
+    }
+
+    /// A hash of the rendered diagram, for detecting when its content changes.
+    ///
+    /// The hash is the 64-bit FNV-1a hash of `to_string()`, so it is stable
+    /// across runs, platforms, and Rust versions and can be stored and compared
+    /// later. Every diagram renders in a deterministic order, so equal diagrams
+    /// always have equal hashes.
+    fn content_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+        self.to_string()
+            .bytes()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Check that mermaid can parse the rendered diagram.
+    ///
+    /// The mermaid CLI doesn't have a parse-only mode, so the diagram is rendered
+    /// to SVG with [`mmdc`](https://github.com/mermaid-js/mermaid-cli) and the image
+    /// is discarded. Requires the `cli` feature and `mmdc` on the `PATH`.
+    ///
+    /// # Errors
+    /// Returns [`RenderError::Failed`] with mermaid's message if the diagram can't
+    /// be parsed, or [`RenderError::MissingCli`] if `mmdc` isn't installed, so
+    /// callers can skip validation when the tool isn't available.
+    #[cfg(feature = "cli")]
+    fn validate_syntax(&self) -> Result<(), RenderError> {
+        crate::cli::validate(&self.to_string())
+    }
+}
+
+impl Diagram for ERD {
+    fn render_with(&self, options: &RenderOptions) -> String {
+        ERD::render_with(self, options)
+    }
+}
+
+impl Diagram for RequirementDiagram {
+    fn render_with(&self, options: &RenderOptions) -> String {
+        RequirementDiagram::render_with(self, options)
+    }
+}
+
+impl Diagram for C4Context {}
+impl Diagram for GitGraph {}
+impl Diagram for Kanban {}
+impl Diagram for Packet {}
+impl Diagram for QuadrantChart {}
+impl Diagram for XYChart {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erd::{Cardinality, Entity, Relationship};
+    use crate::render::LineEnding;
+
+    #[test]
+    fn to_markdown_wraps_diagram_in_fenced_block() {
+        // arrange
+        let wanted = "```mermaid\nkanban\n```";
+        // act
+        let got = Kanban::new().to_markdown();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn content_hash_detects_changes() {
+        // arrange
+        let album_songs = || {
+            ERD::new()
+                .with_entity(Entity::new("ARTIST"))
+                .with_entity(Entity::new("ALBUM"))
+                .with_relationship(Relationship::new(
+                    "ALBUM",
+                    "SONG",
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ))
+        };
+        let changed = album_songs().with_entity(Entity::new("GENRE"));
+        // act & assert
+        assert_eq!(album_songs().content_hash(), album_songs().content_hash());
+        assert_ne!(album_songs().content_hash(), changed.content_hash());
+    }
+
+    #[test]
+    fn content_hash_is_stable() {
+        // act
+        let got = Kanban::new().content_hash();
+        // assert the FNV-1a hash of "kanban" never changes between releases
+        assert_eq!(got, 0x0cc3_19a7_9288_47ee);
+    }
+
+    #[test]
+    fn default_render_with_applies_line_ending() {
+        // arrange
+        let options = RenderOptions::new().with_line_ending(LineEnding::Crlf);
+        let packet = Packet::new().with_title("UDP Packet");
+        // act
+        let got = Diagram::render_with(&packet, &options);
+        // assert
+        assert_eq!(got, "packet-beta\r\n    title UDP Packet");
+    }
+}
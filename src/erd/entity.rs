@@ -2,9 +2,28 @@
 // Entity struct and implementation
 // ==================================================================
 
+use std::collections::HashMap;
 use std::fmt;
 
+use crate::render::RenderOptions;
+use crate::utils::escape_quoted;
+use crate::MormaidError;
+
+/// The layer of a layered data model that an entity belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stereotype {
+    /// The business concepts and how they relate, without attributes or keys.
+    Conceptual,
+    /// The attributes and keys of each entity, independent of a database.
+    Logical,
+    /// The tables and columns of a specific database.
+    Physical,
+}
+
 #[must_use]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Entity {
     /// The id for the entity in the ERD.
     ///
@@ -22,6 +41,28 @@ pub struct Entity {
     /// For many ERDs this is the table name. Unlike id, the alias can be
     /// multiple words separated by spaces.
     pub attributes: Vec<Attribute>, // Use Vec for a dynamic array
+    /// The namespace (e.g. database schema) that the entity belongs to.
+    ///
+    /// Mermaid doesn't support namespaces directly, so entities that share a
+    /// namespace are grouped together between comments when the ERD is rendered.
+    /// Entity ids must still be unique across all namespaces.
+    pub namespace: Option<String>,
+    /// A comment documenting the entity.
+    ///
+    /// The comment is rendered as a `%%` line immediately before the entity,
+    /// so it's preserved in the diagram source but not displayed.
+    pub comment: Option<String>,
+    /// Whether the entity was created because a relationship referenced it,
+    /// rather than being added to the ERD explicitly.
+    ///
+    /// See [`crate::render::RenderOptions::mark_auto_created`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub was_auto_created: bool,
+    /// The modeling layer that the entity belongs to, or `None` if it belongs to all of them.
+    ///
+    /// See [`super::ERD::render_layer()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stereotype: Option<Stereotype>,
 }
 impl Entity {
     /// Create a new Entity with a given id
@@ -30,6 +71,10 @@ impl Entity {
             id: id.to_string(),
             alias: None,
             attributes: Vec::new(), // Initialize with None
+            namespace: None,
+            comment: None,
+            was_auto_created: false,
+            stereotype: None,
         }
     }
 
@@ -39,20 +84,141 @@ impl Entity {
         self
     }
 
+    /// Chain with `Entity::new()` to assign the entity to a namespace
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Chain with `Entity::new()` to document the entity with a comment
+    pub fn with_comment(mut self, comment: &str) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Chain with `Entity::new()` to tag the entity with a modeling layer
+    pub fn with_stereotype(mut self, stereotype: Stereotype) -> Self {
+        self.stereotype = Some(stereotype);
+        self
+    }
+
     /// Add an attribute to an entity
     pub fn with_attribute(mut self, attribute: Attribute) -> Self {
         self.attributes.push(attribute);
         self
     }
+
+    /// Add a named composite unique key made up of several of the entity's attributes.
+    ///
+    /// Mermaid's `UK` constraint only applies to a single attribute, so the group
+    /// is rendered by appending `[unique: <name>]` to the comment of each attribute
+    /// in the group. See [`Attribute::unique_groups`] for details.
+    ///
+    /// # Panics
+    /// This method will panic if one of the attribute names isn't found in the
+    /// entity's list of attributes. Use [`Entity::try_with_unique_group()`] to get
+    /// an error instead.
+    pub fn with_unique_group(self, name: &str, attrs: &[&str]) -> Self {
+        match self.try_with_unique_group(name, attrs) {
+            Ok(entity) => entity,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Add a named composite unique key, see [`Entity::with_unique_group()`].
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingAttribute`] if one of the attribute names
+    /// isn't found in the entity's list of attributes, in which case none of the
+    /// attributes are updated.
+    pub fn try_with_unique_group(
+        mut self,
+        name: &str,
+        attrs: &[&str],
+    ) -> Result<Self, MormaidError> {
+        // Ensure that every attribute exists before updating any of them
+        for attr_name in attrs {
+            if !self.attributes.iter().any(|attr| attr.name == *attr_name) {
+                return Err(MormaidError::MissingAttribute {
+                    entity: self.id.clone(),
+                    attribute: (*attr_name).to_string(),
+                });
+            }
+        }
+        for attr in &mut self.attributes {
+            if attrs.contains(&attr.name.as_str()) {
+                attr.unique_groups.push(name.to_string());
+            }
+        }
+        Ok(self)
+    }
+
+    /// Try to find an attribute of the entity using its name.
+    #[must_use]
+    pub fn attribute_by_name(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|attr| attr.name == name)
+    }
+
+    /// Try to find an attribute of the entity using its name and borrow it
+    /// mutably so it can be updated in place.
+    pub fn attribute_by_name_mut(&mut self, name: &str) -> Option<&mut Attribute> {
+        self.attributes.iter_mut().find(|attr| attr.name == name)
+    }
+
+    /// Iterate over the attributes with the primary keys first, then the foreign
+    /// keys, then the rest, keeping the insertion order within each group.
+    ///
+    /// An attribute that's both a primary and foreign key is yielded with the
+    /// primary keys. The stored order of the attributes is unchanged.
+    pub fn attributes_ordered(&self) -> impl Iterator<Item = &Attribute> {
+        let attrs = &self.attributes;
+        let primary = attrs.iter().filter(|attr| attr.key.is_primary);
+        let foreign = attrs
+            .iter()
+            .filter(|attr| attr.key.is_foreign && !attr.key.is_primary);
+        let rest = attrs
+            .iter()
+            .filter(|attr| !attr.key.is_primary && !attr.key.is_foreign);
+        primary.chain(foreign).chain(rest)
+    }
+
+    /// Collapse attributes that share a name into the first one with that name.
+    ///
+    /// The first attribute keeps its position and type, and absorbs the later
+    /// duplicates as follows:
+    /// - key constraints are combined, so an attribute is a PK if any duplicate is
+    /// - the first non-empty comment is kept
+    /// - metadata keys and unique groups missing from the first attribute are added
+    pub fn dedupe_attributes(&mut self) {
+        let mut deduped: Vec<Attribute> = Vec::new();
+        for attr in self.attributes.drain(..) {
+            match deduped.iter_mut().find(|kept| kept.name == attr.name) {
+                Some(kept) => kept.absorb(attr),
+                None => deduped.push(attr),
+            }
+        }
+        self.attributes = deduped;
+    }
 }
 
-impl fmt::Display for Entity {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Entity {
+    // Render the entity, marking it if it was auto-created and the options ask for it
+    pub(crate) fn render(&self, options: &RenderOptions) -> String {
+        let mut entity_str = String::new();
+        if options.mark_auto_created && self.was_auto_created {
+            entity_str += "%% auto-created\n";
+        }
+        // format the comment (if populated) with each line on its own comment line
+        if let Some(comment) = self.comment.as_deref() {
+            for line in comment.lines() {
+                entity_str += &format!("%% {line}\n");
+            }
+        }
         // format entity id
-        let mut entity_str = self.id.to_string();
+        entity_str += &self.id;
         // format the alias if it exists
         if let Some(alias) = self.alias.as_deref() {
-            entity_str += &format!("[\"{alias}\"]");
+            entity_str += &format!("[\"{}\"]", escape_quoted(alias, options.escape_style));
         }
         // format the attributes if they exist
         if !self.attributes.is_empty() {
@@ -60,12 +226,18 @@ impl fmt::Display for Entity {
             entity_str += " {";
             // append each attribute to a new, indented line
             for attr in &self.attributes {
-                entity_str += &format!("\n    {attr}");
+                entity_str += &format!("\n    {}", attr.render(options));
             }
             // append a final closing bracket on its own line
             entity_str += "\n}";
         }
-        write!(f, "{entity_str}")
+        entity_str
+    }
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(&RenderOptions::default()))
     }
 }
 
@@ -73,11 +245,32 @@ impl fmt::Display for Entity {
 // Attribute struct and implementation
 // ==================================================================
 #[must_use]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attribute {
     pub attr_type: String,
     pub name: String,
     pub key: KeyConstraints,
     pub comment: Option<String>,
+    /// Tool-specific metadata about the attribute, e.g. the source column.
+    ///
+    /// Metadata is never rendered to mermaid, but is included when the
+    /// attribute is serialized with the `serde` feature enabled.
+    pub metadata: HashMap<String, String>,
+    /// The names of the composite unique keys that the attribute is part of.
+    ///
+    /// The group names are rendered at the end of the attribute's comment in the
+    /// format `[unique: <name>, <name>]`, e.g. `"Album title [unique: album_title]"`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unique_groups: Vec<String>,
+    /// The length or precision appended to the type, e.g. the `255` in `varchar(255)`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub type_params: Option<TypeParams>,
+    /// The id of the entity that this foreign key references.
+    ///
+    /// See [`super::ERD::add_reference_relationships()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub references: Option<String>,
 }
 impl Attribute {
     pub fn new(attr_type: &str, name: &str) -> Self {
@@ -86,14 +279,37 @@ impl Attribute {
             name: name.to_string(),
             key: KeyConstraints::default(),
             comment: None,
+            metadata: HashMap::new(),
+            unique_groups: Vec::new(),
+            type_params: None,
+            references: None,
         }
     }
 
+    /// Chain with `Attribute::new()` to add a length to the type, e.g. `varchar(255)`.
+    pub fn with_length(mut self, length: u32) -> Self {
+        self.type_params = Some(TypeParams::Length(length));
+        self
+    }
+
+    /// Chain with `Attribute::new()` to add a precision and scale to the type,
+    /// e.g. `decimal(10-2)` for SQL's `decimal(10,2)`, see [`TypeParams::Precision`].
+    pub fn with_precision(mut self, precision: u32, scale: u32) -> Self {
+        self.type_params = Some(TypeParams::Precision(precision, scale));
+        self
+    }
+
     pub fn with_comment(mut self, comment: &str) -> Self {
         self.comment = Some(comment.to_string());
         self
     }
 
+    /// Add a key-value pair to the attribute's metadata, replacing any existing value for the key.
+    pub fn with_meta(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+
     pub fn as_primary_key(mut self) -> Self {
         self.key.is_primary = true;
         self
@@ -109,25 +325,162 @@ impl Attribute {
         self
     }
 
+    /// Make the attribute a foreign key that references another entity.
+    ///
+    /// Several attributes can reference the same entity, e.g. `created_by` and
+    /// `updated_by` both referencing `USER`, and each one gets its own relationship.
+    pub fn references(mut self, entity_id: &str) -> Self {
+        self.key.is_foreign = true;
+        self.references = Some(entity_id.to_string());
+        self
+    }
+
     #[must_use]
     pub fn has_constraints(&self) -> bool {
         self.key.is_primary || self.key.is_foreign || self.key.is_unique
     }
+
+    /// Check if the attribute is a primary key, see [`Attribute::as_primary_key()`].
+    #[must_use]
+    pub fn is_primary(&self) -> bool {
+        self.key.is_primary
+    }
+
+    /// Check if the attribute is a foreign key, see [`Attribute::as_foreign_key()`].
+    #[must_use]
+    pub fn is_foreign(&self) -> bool {
+        self.key.is_foreign
+    }
+
+    /// Check if the attribute is a unique key, see [`Attribute::as_unique()`].
+    #[must_use]
+    pub fn is_unique(&self) -> bool {
+        self.key.is_unique
+    }
+
+    /// The key constraints rendered as they appear in the attribute, e.g. `PK, FK`.
+    ///
+    /// The label is empty if the attribute has no key constraints.
+    #[must_use]
+    pub fn constraint_label(&self) -> String {
+        self.key.to_string()
+    }
+
+    /// The type as it's rendered in mermaid, with its length or precision,
+    /// e.g. `varchar(255)` or `decimal(10-2)`.
+    #[must_use]
+    pub fn rendered_type(&self) -> String {
+        match &self.type_params {
+            Some(params) => format!("{}{params}", self.attr_type),
+            None => self.attr_type.clone(),
+        }
+    }
+
+    /// The type in SQL syntax, with its length or precision, e.g. `decimal(10,2)`.
+    ///
+    /// Used by the exports to formats that allow a comma in the type, see
+    /// [`TypeParams::to_sql()`].
+    #[must_use]
+    pub fn sql_type(&self) -> String {
+        match &self.type_params {
+            Some(params) => format!("{}{}", self.attr_type, params.to_sql()),
+            None => self.attr_type.clone(),
+        }
+    }
+
+    // Merge a duplicate definition of this attribute, see `Entity::dedupe_attributes()`
+    fn absorb(&mut self, other: Attribute) {
+        self.key.is_primary |= other.key.is_primary;
+        self.key.is_foreign |= other.key.is_foreign;
+        self.key.is_unique |= other.key.is_unique;
+        if self.comment.as_deref().is_none_or(str::is_empty) {
+            if let Some(comment) = other.comment.filter(|c| !c.is_empty()) {
+                self.comment = Some(comment);
+            }
+        }
+        if self.references.is_none() {
+            self.references = other.references;
+        }
+        for (key, value) in other.metadata {
+            self.metadata.entry(key).or_insert(value);
+        }
+        for group in other.unique_groups {
+            if !self.unique_groups.contains(&group) {
+                self.unique_groups.push(group);
+            }
+        }
+    }
+
+    // Combine the comment with the names of the attribute's unique groups
+    fn rendered_comment(&self) -> Option<String> {
+        if self.unique_groups.is_empty() {
+            return self.comment.clone();
+        }
+        let groups = format!("[unique: {}]", self.unique_groups.join(", "));
+        match self.comment.as_deref() {
+            Some(comment) => Some(format!("{comment} {groups}")),
+            None => Some(groups),
+        }
+    }
 }
 
-impl fmt::Display for Attribute {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // format the attribute type and name
-        let mut attr_str = format!("{} {}", self.attr_type, self.name);
+impl Attribute {
+    // Render the attribute, escaping its comment with the style in the options
+    pub(crate) fn render(&self, options: &RenderOptions) -> String {
+        // format the attribute type, with its length or precision, and name
+        let mut attr_str = format!("{} {}", self.rendered_type(), self.name);
         // format key constraints if any exist
         if self.has_constraints() {
             attr_str += &format!(" {}", self.key);
         }
-        // format the comment if one exists
-        if let Some(comment) = self.comment.as_deref() {
-            attr_str += &format!(" \"{comment}\"");
+        // format the comment, including any unique groups, if one exists
+        if let Some(comment) = self.rendered_comment() {
+            attr_str += &format!(" \"{}\"", escape_quoted(&comment, options.escape_style));
+        }
+        attr_str
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(&RenderOptions::default()))
+    }
+}
+
+// ==================================================================
+// TypeParams enum
+// ==================================================================
+
+/// The parameters of a sized or scaled attribute type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TypeParams {
+    /// A maximum length, rendered as `(255)`.
+    Length(u32),
+    /// A precision and scale, rendered as `(10-2)`.
+    ///
+    /// Mermaid's attribute types can't contain a comma, so the `,` that separates
+    /// the precision and scale in SQL, e.g. `decimal(10,2)`, is rendered as a `-`.
+    Precision(u32, u32),
+}
+
+impl TypeParams {
+    /// The parameters in SQL syntax, e.g. `(255)` or `(10,2)`.
+    #[must_use]
+    pub fn to_sql(&self) -> String {
+        match self {
+            TypeParams::Length(length) => format!("({length})"),
+            TypeParams::Precision(precision, scale) => format!("({precision},{scale})"),
+        }
+    }
+}
+
+impl fmt::Display for TypeParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeParams::Length(length) => write!(f, "({length})"),
+            TypeParams::Precision(precision, scale) => write!(f, "({precision}-{scale})"),
         }
-        write!(f, "{attr_str}")
     }
 }
 
@@ -162,6 +515,8 @@ impl ConstraintCombo {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyConstraints {
     pub is_primary: bool,
     pub is_foreign: bool,
@@ -235,6 +590,14 @@ mod tests {
             assert_eq!(entity.alias, Some(ALIAS.to_string()));
         }
 
+        #[test]
+        fn test_create_with_namespace() {
+            // act
+            let entity = Entity::new(ENTITY_ID).with_namespace("music");
+            // assert
+            assert_eq!(entity.namespace, Some("music".to_string()));
+        }
+
         #[test]
         fn test_with_attribute() {
             // act
@@ -267,6 +630,90 @@ mod tests {
             assert_eq!(got, wanted);
         }
 
+        #[test]
+        fn test_find_attribute_by_name() {
+            // arrange
+            let mut entity =
+                Entity::new(ENTITY_ID).with_attribute(Attribute::new(ATTR_TYPE, ATTR_NAME));
+            // act
+            if let Some(attr) = entity.attribute_by_name_mut(ATTR_NAME) {
+                attr.key.is_unique = true;
+            }
+            // assert
+            let attr = entity
+                .attribute_by_name(ATTR_NAME)
+                .expect("Expected attribute");
+            assert!(attr.key.is_unique);
+            assert!(entity.attribute_by_name("missing").is_none());
+            assert!(entity.attribute_by_name_mut("missing").is_none());
+        }
+
+        #[test]
+        fn test_display_with_comment() {
+            // arrange
+            let entity = Entity::new(ENTITY_ID)
+                .with_comment("Albums released by an artist")
+                .with_attribute(Attribute::new(ATTR_TYPE, ATTR_NAME));
+            let wanted = concat!(
+                "%% Albums released by an artist\n",
+                "ALBUM {\n",
+                "    string title\n",
+                "}"
+            );
+            // act
+            let got = entity.to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
+
+        #[test]
+        fn test_display_with_composite_unique_group() {
+            // arrange
+            let entity = Entity::new(ENTITY_ID)
+                .with_attribute(Attribute::new("int", "artist_id").as_foreign_key())
+                .with_attribute(Attribute::new(ATTR_TYPE, ATTR_NAME).with_comment("Album title"))
+                .with_attribute(Attribute::new("int", "year"))
+                .with_unique_group("artist_title", &["artist_id", ATTR_NAME]);
+            let wanted = concat!(
+                "ALBUM {\n",
+                "    int artist_id FK \"[unique: artist_title]\"\n",
+                "    string title \"Album title [unique: artist_title]\"\n",
+                "    int year\n",
+                "}"
+            );
+            // act
+            let got = entity.to_string();
+            // assert
+            assert_eq!(got, wanted);
+            assert!(!entity.attributes[0].key.is_unique); // the column alone isn't unique
+        }
+
+        #[test]
+        #[should_panic = "year isn't found in the list of attributes for ALBUM"]
+        fn test_unique_group_with_missing_attribute_should_panic() {
+            // act
+            let _ = Entity::new(ENTITY_ID)
+                .with_attribute(Attribute::new(ATTR_TYPE, ATTR_NAME))
+                .with_unique_group("title_year", &[ATTR_NAME, "year"]);
+        }
+
+        #[test]
+        fn test_try_unique_group_with_missing_attribute_should_error() {
+            // act
+            let got = Entity::new(ENTITY_ID)
+                .with_attribute(Attribute::new(ATTR_TYPE, ATTR_NAME))
+                .try_with_unique_group("title_year", &[ATTR_NAME, "year"])
+                .map(|entity| entity.attributes.len());
+            // assert
+            assert_eq!(
+                got,
+                Err(MormaidError::MissingAttribute {
+                    entity: ENTITY_ID.to_string(),
+                    attribute: "year".to_string(),
+                })
+            );
+        }
+
         #[test]
         fn test_display_with_multiple_attributes() {
             // arrange
@@ -286,6 +733,40 @@ mod tests {
             // assert
             assert_eq!(got, wanted);
         }
+
+        #[test]
+        fn test_attributes_ordered_by_constraint() {
+            // arrange
+            let entity = Entity::new(ENTITY_ID)
+                .with_attribute(Attribute::new("string", "title"))
+                .with_attribute(Attribute::new("int", "artist_id").as_foreign_key())
+                .with_attribute(Attribute::new("int", "album_id").as_primary_key())
+                .with_attribute(Attribute::new("string", "sku").as_unique())
+                .with_attribute(Attribute::new("int", "label_id").as_foreign_key())
+                .with_attribute(
+                    Attribute::new("int", "release_id")
+                        .as_primary_key()
+                        .as_foreign_key(),
+                );
+            // act
+            let got: Vec<&str> = entity
+                .attributes_ordered()
+                .map(|attr| attr.name.as_str())
+                .collect();
+            // assert
+            assert_eq!(
+                got,
+                vec![
+                    "album_id",
+                    "release_id",
+                    "artist_id",
+                    "label_id",
+                    "title",
+                    "sku"
+                ]
+            );
+            assert_eq!(entity.attributes[0].name, "title");
+        }
     }
 
     // =========================
@@ -293,6 +774,7 @@ mod tests {
     // =========================
     mod attribute_tests {
         use super::*;
+        use crate::render::EscapeStyle;
         #[test]
         fn test_create_without_comment_or_key_constraints() {
             // act
@@ -329,6 +811,27 @@ mod tests {
             assert!(attr.key.is_unique);
         }
 
+        #[test]
+        fn test_key_accessors_match_flags() {
+            for combination in 0..8 {
+                // arrange
+                let (primary, foreign, unique) = (
+                    combination & 1 != 0,
+                    combination & 2 != 0,
+                    combination & 4 != 0,
+                );
+                let mut attr = Attribute::new(ATTR_TYPE, ATTR_NAME);
+                attr.key.is_primary = primary;
+                attr.key.is_foreign = foreign;
+                attr.key.is_unique = unique;
+                // act
+                let got = (attr.is_primary(), attr.is_foreign(), attr.is_unique());
+                // assert
+                assert_eq!(got, (primary, foreign, unique));
+                assert_eq!(attr.has_constraints(), primary || foreign || unique);
+            }
+        }
+
         #[test]
         fn test_create_with_comment() {
             // arrange
@@ -339,6 +842,61 @@ mod tests {
             assert_eq!(attr.comment, Some(comment.to_string()));
         }
 
+        #[test]
+        fn test_create_with_metadata() {
+            // act
+            let attr = Attribute::new(ATTR_TYPE, ATTR_NAME)
+                .with_meta("source_column", "album_title")
+                .with_meta("pii", "false");
+            // assert
+            assert_eq!(attr.metadata.len(), 2);
+            assert_eq!(attr.metadata["source_column"], "album_title");
+        }
+
+        #[test]
+        fn test_metadata_is_not_displayed() {
+            // arrange
+            let wanted = format!("{ATTR_TYPE} {ATTR_NAME} PK");
+            let attr = Attribute::new(ATTR_TYPE, ATTR_NAME)
+                .as_primary_key()
+                .with_meta("pii", "true");
+            // act
+            let got = attr.to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
+
+        #[test]
+        #[cfg(feature = "serde")]
+        fn test_metadata_round_trips_with_serde() {
+            // arrange
+            let attr = Attribute::new(ATTR_TYPE, ATTR_NAME).with_meta("pii", "true");
+            // act
+            let json = serde_json::to_string(&attr).expect("Expected attribute to serialize");
+            let got: Attribute = serde_json::from_str(&json).expect("Expected JSON to parse");
+            // assert
+            assert_eq!(got.name, ATTR_NAME);
+            assert_eq!(got.metadata, attr.metadata);
+        }
+
+        #[test]
+        #[cfg(feature = "serde")]
+        fn test_deserialize_attribute_without_later_fields() {
+            // arrange
+            let json = concat!(
+                r#"{"attr_type":"string","name":"title","#,
+                r#""key":{"is_primary":false,"is_foreign":false,"is_unique":false},"#,
+                r#""comment":null,"metadata":{}}"#,
+            );
+            // act
+            let got: Attribute = serde_json::from_str(json).expect("Expected JSON to parse");
+            // assert
+            assert_eq!(got.name, "title");
+            assert!(got.unique_groups.is_empty());
+            assert_eq!(got.type_params, None);
+            assert_eq!(got.references, None);
+        }
+
         #[test]
         fn test_display_without_key_or_comment() {
             // arrange
@@ -375,6 +933,21 @@ mod tests {
             assert_eq!(got, wanted);
         }
 
+        #[test]
+        fn test_display_with_key_constraint_and_comment() {
+            // arrange
+            let comment = "comment about album";
+            let wanted = format!("{ATTR_TYPE} {ATTR_NAME} PK, UK \"{comment}\"");
+            let attr = Attribute::new(ATTR_TYPE, ATTR_NAME)
+                .as_primary_key()
+                .as_unique()
+                .with_comment(comment);
+            // act
+            let got = attr.to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
+
         #[test]
         fn test_display_with_comment() {
             // arrange
@@ -386,5 +959,110 @@ mod tests {
             // assert
             assert_eq!(got, wanted);
         }
+
+        #[test]
+        fn test_display_comment_placement_matches_mermaid_grammar() {
+            // arrange
+            // mermaid's grammar is `type name [keys] ["comment"]`, so the comment
+            // follows the name directly when there are no keys
+            let cases = [
+                (
+                    Attribute::new(ATTR_TYPE, ATTR_NAME)
+                        .as_primary_key()
+                        .with_comment("c"),
+                    "string title PK \"c\"",
+                ),
+                (
+                    Attribute::new(ATTR_TYPE, ATTR_NAME).with_comment("c"),
+                    "string title \"c\"",
+                ),
+                (
+                    Attribute::new(ATTR_TYPE, ATTR_NAME).as_primary_key(),
+                    "string title PK",
+                ),
+                (Attribute::new(ATTR_TYPE, ATTR_NAME), "string title"),
+            ];
+            for (attr, wanted) in cases {
+                // act
+                let got = attr.to_string();
+                // assert
+                assert_eq!(got, wanted);
+            }
+        }
+
+        #[test]
+        fn test_display_with_length() {
+            // act
+            let got = Attribute::new("varchar", "title")
+                .with_length(255)
+                .to_string();
+            // assert
+            assert_eq!(got, "varchar(255) title");
+        }
+
+        #[test]
+        fn test_display_with_precision() {
+            // act
+            let got = Attribute::new("decimal", "price")
+                .with_precision(10, 2)
+                .as_unique()
+                .to_string();
+            // assert
+            assert_eq!(got, "decimal(10-2) price UK");
+        }
+
+        #[test]
+        fn test_constraint_label_for_each_combination() {
+            // arrange
+            let base = || Attribute::new(ATTR_TYPE, ATTR_NAME);
+            let attrs = [
+                (base(), ""),
+                (base().as_primary_key(), "PK"),
+                (base().as_foreign_key(), "FK"),
+                (base().as_unique(), "UK"),
+                (base().as_primary_key().as_foreign_key(), "PK, FK"),
+                (base().as_primary_key().as_unique(), "PK, UK"),
+                (base().as_foreign_key().as_unique(), "FK, UK"),
+                (
+                    base().as_primary_key().as_foreign_key().as_unique(),
+                    "PK, FK, UK",
+                ),
+            ];
+            for (attr, wanted) in attrs {
+                // act
+                let got = attr.constraint_label();
+                // assert
+                assert_eq!(got, wanted);
+            }
+        }
+
+        #[test]
+        fn test_render_comment_with_each_escape_style() {
+            // arrange
+            let attr = Attribute::new(ATTR_TYPE, ATTR_NAME).with_comment(r#"the "best" album"#);
+            let styles = [
+                (EscapeStyle::Entity, r#""the #quot;best#quot; album""#),
+                (EscapeStyle::Html, r#""the &quot;best&quot; album""#),
+                (EscapeStyle::Backslash, r#""the \"best\" album""#),
+            ];
+            for (style, comment) in styles {
+                let options = RenderOptions::new().with_escape_style(style);
+                // act
+                let got = attr.render(&options);
+                // assert
+                assert_eq!(got, format!("{ATTR_TYPE} {ATTR_NAME} {comment}"));
+            }
+        }
+
+        #[test]
+        fn test_display_escapes_hash_and_quote_in_comment() {
+            // arrange
+            let attr = Attribute::new(ATTR_TYPE, ATTR_NAME).with_comment(r##"the "#1" album"##);
+            let wanted = format!("{ATTR_TYPE} {ATTR_NAME} \"the #quot;#35;1#quot; album\"");
+            // act
+            let got = attr.to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
     }
 }
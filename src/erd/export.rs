@@ -0,0 +1,456 @@
+// ==================================================================
+// Implement ERD methods to export the diagram to other formats
+// ==================================================================
+
+use super::{Direction, Entity, ERD};
+use crate::render::EscapeStyle;
+use crate::utils;
+#[cfg(feature = "cli")]
+use crate::RenderError;
+
+/// The script that loads mermaid from a CDN and renders every `mermaid` block on the page.
+const MERMAID_SCRIPT: &str = concat!(
+    "<script type=\"module\">\n",
+    "    import mermaid from 'https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs';\n",
+    "    mermaid.initialize({ startOnLoad: true });\n",
+    "</script>",
+);
+
+impl ERD {
+    /// Render the ERD as an HTML fragment that mermaid can render in the browser.
+    ///
+    /// The diagram is HTML-escaped and wrapped in a `<pre class="mermaid">` block.
+    /// The page must also load mermaid, see [`ERD::to_html_with_script()`].
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let body = utils::escape_html(&self.to_string());
+        format!("<pre class=\"mermaid\">\n{body}\n</pre>")
+    }
+
+    /// Render the ERD as an HTML fragment followed by the script that loads mermaid.
+    ///
+    /// The script imports mermaid from the jsDelivr CDN, so the fragment can be
+    /// dropped into any page that doesn't already load mermaid.
+    #[must_use]
+    pub fn to_html_with_script(&self) -> String {
+        format!("{}\n{MERMAID_SCRIPT}", self.to_html())
+    }
+
+    /// Render a markdown data dictionary with a table of each entity's attributes.
+    ///
+    /// Each entity gets a `##` heading (with its alias in parentheses, if it has one),
+    /// its comment (if it has one), and a table with the name, type, key
+    /// constraints, and comment of each attribute, with `|` escaped and line
+    /// breaks rendered as `<br/>` so each attribute stays on one row. Entities are
+    /// listed in the same order they're rendered in the diagram, and the ERD's
+    /// title (if it has one) is used as a `#` heading.
+    #[must_use]
+    pub fn to_data_dictionary(&self) -> String {
+        let mut sections: Vec<String> = Vec::new();
+        if let Some(title) = self.title.as_deref() {
+            sections.push(format!("# {title}"));
+        }
+        for entity in self {
+            let mut heading = format!("## {}", entity.id);
+            if let Some(alias) = entity.alias.as_deref() {
+                heading += &format!(" ({alias})");
+            }
+            sections.push(heading);
+            if let Some(comment) = entity.comment.as_deref() {
+                sections.push(comment.to_string());
+            }
+            if entity.attributes.is_empty() {
+                sections.push("No attributes.".to_string());
+                continue;
+            }
+            let mut table = String::from("| Name | Type | Constraints | Comment |\n");
+            table += "| --- | --- | --- | --- |";
+            for attr in &entity.attributes {
+                let cells = [
+                    attr.name.as_str(),
+                    &attr.sql_type(),
+                    &attr.constraint_label(),
+                    attr.comment.as_deref().unwrap_or_default(),
+                ]
+                .map(|cell| cell.replace('|', "\\|").replace('\n', "<br/>"));
+                table += &format!("\n| {} |", cells.join(" | "));
+            }
+            sections.push(table);
+        }
+        sections.join("\n\n")
+    }
+
+    /// Render the ERD as a [PlantUML](https://plantuml.com/ie-diagram) entity-relationship diagram.
+    ///
+    /// Each entity becomes an `entity` block (with its alias as the displayed name,
+    /// if it has one) that lists its attributes as `name : type` with `<<PK>>`,
+    /// `<<FK>>`, and `<<UK>>` stereotypes. The crow's foot glyphs match
+    /// mermaid's, so relationships use the same [`super::Cardinality::symbol()`]
+    /// glyphs, with `--` for identifying and `..` for non-identifying relationships.
+    /// Quotes are dropped from aliases and labels, since the format can't escape them.
+    #[must_use]
+    pub fn to_plantuml(&self) -> String {
+        let mut out_str = String::from("@startuml");
+        for entity in self {
+            out_str += "\n";
+            match entity.alias.as_deref() {
+                Some(alias) => {
+                    out_str += &format!("entity \"{}\" as {}", plantuml_text(alias), entity.id);
+                }
+                None => out_str += &format!("entity {}", entity.id),
+            }
+            if entity.attributes.is_empty() {
+                continue;
+            }
+            out_str += " {";
+            for attr in &entity.attributes {
+                out_str += &format!("\n  {} : {}", attr.name, attr.sql_type());
+                for (is_key, key) in [
+                    (attr.key.is_primary, "PK"),
+                    (attr.key.is_foreign, "FK"),
+                    (attr.key.is_unique, "UK"),
+                ] {
+                    if is_key {
+                        out_str += &format!(" <<{key}>>");
+                    }
+                }
+            }
+            out_str += "\n}";
+        }
+        for r in &self.relationships {
+            let line = if r.is_identifying { "--" } else { ".." };
+            out_str += &format!(
+                "\n{} {}{line}{} {}",
+                r.left_id.as_str(),
+                r.left_cardinality.symbol(Direction::Left),
+                r.right_cardinality.symbol(Direction::Right),
+                r.right_id.as_str(),
+            );
+            if let Some(label) = r.label() {
+                out_str += &format!(" : {}", plantuml_text(label));
+            }
+        }
+        out_str + "\n@enduml"
+    }
+
+    /// Render the ERD as a [Graphviz](https://graphviz.org/doc/info/lang.html) DOT
+    /// graph, with the entities of each namespace in their own cluster.
+    ///
+    /// Each namespace becomes a `subgraph "cluster_<namespace>"` block labeled with
+    /// the namespace, and entities without a namespace are nodes in the root graph.
+    /// Nodes are labeled with the entity's alias (or id if it doesn't have one).
+    /// Relationships are edges from the left to the right entity, even when they
+    /// cross clusters, labeled with the relationship's label and dashed if they're
+    /// non-identifying.
+    #[must_use]
+    pub fn to_dot_clustered(&self) -> String {
+        let mut out_str = String::from("digraph ERD {\n    node [shape=box];");
+        let mut namespace: Option<&str> = None;
+        for entity in self {
+            // close the previous cluster and open a new one when the namespace changes
+            if entity.namespace.as_deref() != namespace {
+                if namespace.is_some() {
+                    out_str += "\n    }";
+                }
+                namespace = entity.namespace.as_deref();
+                if let Some(ns) = namespace {
+                    out_str += &format!(
+                        "\n    subgraph {} {{\n        label={};",
+                        dot_quoted(&format!("cluster_{ns}")),
+                        dot_quoted(ns),
+                    );
+                }
+            }
+            let indent = if namespace.is_some() { 8 } else { 4 };
+            out_str += &format!("\n{}{}", " ".repeat(indent), dot_node(entity));
+        }
+        if namespace.is_some() {
+            out_str += "\n    }";
+        }
+        for r in &self.relationships {
+            let mut attrs = Vec::new();
+            if let Some(label) = r.label() {
+                attrs.push(format!("label={}", dot_quoted(label)));
+            }
+            if !r.is_identifying {
+                attrs.push("style=dashed".to_string());
+            }
+            out_str += &format!(
+                "\n    {} -> {}",
+                dot_quoted(r.left_id.as_str()),
+                dot_quoted(r.right_id.as_str()),
+            );
+            if !attrs.is_empty() {
+                out_str += &format!(" [{}]", attrs.join(", "));
+            }
+            out_str += ";";
+        }
+        out_str + "\n}"
+    }
+
+    /// Render the ERD to a PNG image with the mermaid CLI and return its bytes.
+    ///
+    /// Requires the `cli` feature and [`mmdc`](https://github.com/mermaid-js/mermaid-cli)
+    /// on the `PATH`. Base64-encode the bytes to embed the image in an email or web page.
+    ///
+    /// # Errors
+    /// Returns [`RenderError::MissingCli`] if `mmdc` isn't installed, or another
+    /// [`RenderError`] if the diagram can't be rendered.
+    #[cfg(feature = "cli")]
+    pub fn render_png(&self) -> Result<Vec<u8>, RenderError> {
+        crate::cli::render_image(&self.to_string(), "png")
+    }
+}
+
+// Format an entity as a DOT node labeled with its alias or id
+fn dot_node(entity: &Entity) -> String {
+    let label = entity.alias.as_deref().unwrap_or(&entity.id);
+    format!("{} [label={}];", dot_quoted(&entity.id), dot_quoted(label))
+}
+
+// Quote a DOT id, escaping the backslashes and quotes inside it
+fn dot_quoted(text: &str) -> String {
+    format!("\"{}\"", utils::escape_quoted(text, EscapeStyle::Backslash))
+}
+
+// Strip the quotes that would end a PlantUML name early and write line breaks as `\n`,
+// since PlantUML has no escape for a quote inside a quoted name
+fn plantuml_text(text: &str) -> String {
+    text.replace('"', "").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn album_songs() -> ERD {
+        ERD::new().with_relationship(
+            Relationship::new(
+                "ALBUM",
+                "SONG",
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            )
+            .with_label("<includes>"),
+        )
+    }
+
+    #[test]
+    fn html_has_mermaid_class_and_escaped_body() {
+        // arrange
+        let wanted = concat!(
+            "<pre class=\"mermaid\">\n",
+            "erDiagram\n",
+            "    %% Entities start\n",
+            "    ALBUM\n",
+            "    SONG\n",
+            "    %% Entities end\n",
+            "    %% Relationships start\n",
+            "    ALBUM ||--|{ SONG : \"&lt;includes&gt;\"\n",
+            "    %% Relationships end\n",
+            "</pre>",
+        );
+        // act
+        let got = album_songs().to_html();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn html_with_script_initializes_mermaid() {
+        // arrange
+        let wanted = concat!(
+            "<pre class=\"mermaid\">\n",
+            "erDiagram\n",
+            "    %% Entities start\n",
+            "    ALBUM\n",
+            "    SONG\n",
+            "    %% Entities end\n",
+            "    %% Relationships start\n",
+            "    ALBUM ||--|{ SONG : \"&lt;includes&gt;\"\n",
+            "    %% Relationships end\n",
+            "</pre>\n",
+            "<script type=\"module\">\n",
+            "    import mermaid from 'https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs';\n",
+            "    mermaid.initialize({ startOnLoad: true });\n",
+            "</script>",
+        );
+        // act
+        let got = album_songs().to_html_with_script();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn data_dictionary_lists_each_attribute() {
+        // arrange
+        let mut erd = ERD::new()
+            .with_entity(
+                Entity::new("ALBUM")
+                    .with_alias("album")
+                    .with_comment("Albums released by an artist")
+                    .with_attribute(Attribute::new("int", "albumId").as_primary_key())
+                    .with_attribute(Attribute::new("int", "artistId").as_foreign_key())
+                    .with_attribute(
+                        Attribute::new("varchar", "title")
+                            .with_length(255)
+                            .with_comment("Title | subtitle\nof the album"),
+                    )
+                    .with_attribute(Attribute::new("decimal", "price").with_precision(10, 2)),
+            )
+            .with_entity(Entity::new("GENRE"));
+        erd.title = Some("Music".to_string());
+        let wanted = concat!(
+            "# Music\n\n",
+            "## ALBUM (album)\n\n",
+            "Albums released by an artist\n\n",
+            "| Name | Type | Constraints | Comment |\n",
+            "| --- | --- | --- | --- |\n",
+            "| albumId | int | PK |  |\n",
+            "| artistId | int | FK |  |\n",
+            "| title | varchar(255) |  | Title \\| subtitle<br/>of the album |\n",
+            "| price | decimal(10,2) |  |  |\n\n",
+            "## GENRE\n\n",
+            "No attributes.",
+        );
+        // act
+        let got = erd.to_data_dictionary();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn plantuml_has_entities_and_crows_foot_relationships() {
+        // arrange
+        let erd = ERD::new()
+            .with_entity(
+                Entity::new("ALBUM")
+                    .with_alias("album")
+                    .with_attribute(Attribute::new("int", "albumId").as_primary_key())
+                    .with_attribute(Attribute::new("varchar", "title").with_length(255))
+                    .with_attribute(Attribute::new("decimal", "price").with_precision(10, 2)),
+            )
+            .with_entity(
+                Entity::new("SONG")
+                    .with_attribute(Attribute::new("int", "songId").as_primary_key())
+                    .with_attribute(Attribute::new("int", "albumId").as_foreign_key()),
+            )
+            .with_relationship(
+                Relationship::new(
+                    "ALBUM",
+                    "SONG",
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                )
+                .with_label("includes"),
+            )
+            .with_relationship(
+                Relationship::new(
+                    "SONG",
+                    "ALBUM",
+                    Cardinality::OneOrMore,
+                    Cardinality::ZeroOrOne,
+                )
+                .as_non_identifying(),
+            );
+        let wanted = concat!(
+            "@startuml\n",
+            "entity \"album\" as ALBUM {\n",
+            "  albumId : int <<PK>>\n",
+            "  title : varchar(255)\n",
+            "  price : decimal(10,2)\n",
+            "}\n",
+            "entity SONG {\n",
+            "  songId : int <<PK>>\n",
+            "  albumId : int <<FK>>\n",
+            "}\n",
+            "ALBUM ||--o{ SONG : includes\n",
+            "SONG }|..o| ALBUM\n",
+            "@enduml",
+        );
+        // act
+        let got = erd.to_plantuml();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn plantuml_strips_quotes_from_aliases_and_labels() {
+        // arrange
+        let erd = ERD::new()
+            .with_entity(Entity::new("BIG").with_alias("My \"big\" table"))
+            .with_relationship(
+                Relationship::new(
+                    "BIG",
+                    "SMALL",
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                )
+                .with_label("has \"small\"\nrows"),
+            );
+        let wanted = concat!(
+            "@startuml\n",
+            "entity \"My big table\" as BIG\n",
+            "entity SMALL\n",
+            "BIG ||--o{ SMALL : has small\\nrows\n",
+            "@enduml",
+        );
+        // act
+        let got = erd.to_plantuml();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn dot_clusters_entities_by_namespace() {
+        // arrange
+        let erd = ERD::new()
+            .with_entity(Entity::new("AUDIT_LOG"))
+            .with_entity(
+                Entity::new("ALBUM")
+                    .with_alias("album")
+                    .with_namespace("music"),
+            )
+            .with_entity(Entity::new("SONG").with_namespace("music"))
+            .with_entity(Entity::new("USER").with_namespace("auth"))
+            .with_relationship(
+                Relationship::new(
+                    "ALBUM",
+                    "SONG",
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                )
+                .with_label("includes"),
+            )
+            .with_relationship(
+                Relationship::new(
+                    "USER",
+                    "ALBUM",
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                )
+                .as_non_identifying(),
+            );
+        let wanted = concat!(
+            "digraph ERD {\n",
+            "    node [shape=box];\n",
+            "    \"AUDIT_LOG\" [label=\"AUDIT_LOG\"];\n",
+            "    subgraph \"cluster_auth\" {\n",
+            "        label=\"auth\";\n",
+            "        \"USER\" [label=\"USER\"];\n",
+            "    }\n",
+            "    subgraph \"cluster_music\" {\n",
+            "        label=\"music\";\n",
+            "        \"ALBUM\" [label=\"album\"];\n",
+            "        \"SONG\" [label=\"SONG\"];\n",
+            "    }\n",
+            "    \"ALBUM\" -> \"SONG\" [label=\"includes\"];\n",
+            "    \"USER\" -> \"ALBUM\" [style=dashed];\n",
+            "}",
+        );
+        // act
+        let got = erd.to_dot_clustered();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+}
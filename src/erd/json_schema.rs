@@ -0,0 +1,308 @@
+// ==================================================================
+// Build an ERD from a JSON Schema document
+// ==================================================================
+
+use serde_json::{Map, Value};
+
+use super::{Attribute, Cardinality, Entity, Relationship, ERD};
+use crate::ParseError;
+
+impl ERD {
+    /// Build an ERD from the object definitions in a JSON Schema document.
+    ///
+    /// Each definition under `$defs` (or `definitions`) with `"type": "object"`
+    /// becomes an entity with the same id. If the schema has no definitions, the
+    /// root object becomes an entity named after its `title`. Then for each property:
+    /// - a property with a JSON `type` becomes an attribute of that type, and
+    ///   properties listed in `required` get the metadata `nullable: false`
+    /// - a `$ref` to another object definition becomes a relationship to that entity,
+    ///   exactly one if the property is required and zero or one otherwise, and a
+    ///   `$ref` to any other definition becomes an attribute of that definition's type
+    /// - an `array` whose `items` are a `$ref` to an object definition becomes a
+    ///   relationship that's zero or more on both ends, since nothing stops several
+    ///   objects from listing the same item
+    ///
+    /// Attributes are listed in the order the properties appear in the schema, and
+    /// relationships are labeled with the property name. Requires the `json` feature.
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] if the document isn't valid JSON, isn't an object
+    /// with definitions or a titled root object, or has a `$ref` that isn't a local
+    /// `#/$defs/<name>` (or `#/definitions/<name>`) reference to an existing
+    /// definition. Only invalid JSON is reported with a line number; every other
+    /// error is reported on line 0.
+    pub fn from_json_schema(schema: &str) -> Result<ERD, ParseError> {
+        let root: Value = serde_json::from_str(schema)
+            .map_err(|err| ParseError::new(err.line(), &err.to_string()))?;
+        let definitions = definitions(&root)?;
+        let mut erd = ERD::new();
+        for (name, definition) in &definitions {
+            if !is_object(definition) {
+                continue;
+            }
+            let (entity, relationships) = parse_object(name, definition, &definitions)?;
+            erd.add_entity(entity);
+            erd = erd.with_relationships(relationships);
+        }
+        Ok(erd)
+    }
+}
+
+// Collect the named definitions, falling back to a titled root object
+fn definitions(root: &Value) -> Result<Map<String, Value>, ParseError> {
+    let defs = root.get("$defs").or_else(|| root.get("definitions"));
+    if let Some(defs) = defs {
+        return defs
+            .as_object()
+            .cloned()
+            .ok_or_else(|| ParseError::new(0, "expected the definitions to be an object"));
+    }
+    match root.get("title").and_then(Value::as_str) {
+        Some(title) => Ok(Map::from_iter([(title.to_string(), root.clone())])),
+        None => Err(ParseError::new(
+            0,
+            "expected $defs, definitions, or a titled root object",
+        )),
+    }
+}
+
+// Map an object definition to an entity and the relationships from its $ref properties
+fn parse_object(
+    name: &str,
+    definition: &Value,
+    definitions: &Map<String, Value>,
+) -> Result<(Entity, Vec<Relationship>), ParseError> {
+    let required: Vec<&str> = definition
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut entity = Entity::new(name);
+    let mut relationships = Vec::new();
+    let Some(properties) = definition.get("properties").and_then(Value::as_object) else {
+        return Ok((entity, relationships));
+    };
+    for (prop_name, prop) in properties {
+        let is_required = required.contains(&prop_name.as_str());
+        // a reference to a single object
+        let reference = resolve_ref(prop, definitions)?;
+        if let Some((target, _)) = reference.filter(|(_, definition)| is_object(definition)) {
+            let right = if is_required {
+                Cardinality::ExactlyOne
+            } else {
+                Cardinality::ZeroOrOne
+            };
+            relationships.push(
+                Relationship::new(name, target, Cardinality::ZeroOrMore, right)
+                    .with_label(prop_name),
+            );
+            continue;
+        }
+        // a reference to any other definition is an attribute of the definition's type
+        let attr_type = reference
+            .map_or(prop, |(_, definition)| definition)
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("any");
+        // an array of references to objects
+        if attr_type == "array" {
+            if let Some(items) = prop.get("items") {
+                let item_ref = resolve_ref(items, definitions)?;
+                if let Some((target, _)) = item_ref.filter(|(_, definition)| is_object(definition))
+                {
+                    relationships.push(
+                        Relationship::new(
+                            name,
+                            target,
+                            Cardinality::ZeroOrMore,
+                            Cardinality::ZeroOrMore,
+                        )
+                        .with_label(prop_name),
+                    );
+                    continue;
+                }
+            }
+        }
+        let nullable = if is_required { "false" } else { "true" };
+        entity = entity
+            .with_attribute(Attribute::new(attr_type, prop_name).with_meta("nullable", nullable));
+    }
+    Ok((entity, relationships))
+}
+
+// Check if a definition describes an object, which becomes an entity
+fn is_object(definition: &Value) -> bool {
+    definition.get("type").and_then(Value::as_str) == Some("object")
+}
+
+// Get the name and definition that a local `$ref` points to, if the value has one
+fn resolve_ref<'a>(
+    value: &Value,
+    definitions: &'a Map<String, Value>,
+) -> Result<Option<(&'a str, &'a Value)>, ParseError> {
+    let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+        return Ok(None);
+    };
+    let Some(name) = reference
+        .strip_prefix("#/$defs/")
+        .or_else(|| reference.strip_prefix("#/definitions/"))
+    else {
+        return Err(ParseError::new(
+            0,
+            &format!("{reference} isn't a local reference to a definition"),
+        ));
+    };
+    match definitions.get_key_value(name) {
+        Some((name, definition)) => Ok(Some((name.as_str(), definition))),
+        None => Err(ParseError::new(
+            0,
+            &format!("{reference} isn't found in the definitions"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::ParseError;
+
+    const SCHEMA: &str = r##"{
+        "$defs": {
+            "Album": {
+                "type": "object",
+                "required": ["id", "artist"],
+                "properties": {
+                    "id": { "type": "integer" },
+                    "title": { "type": "string" },
+                    "artist": { "$ref": "#/$defs/Artist" },
+                    "featuring": { "type": "array", "items": { "$ref": "#/$defs/Artist" } }
+                }
+            },
+            "Artist": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" }
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn build_erd_from_referencing_objects() {
+        // arrange
+        let wanted = concat!(
+            "erDiagram\n",
+            "    %% Entities start\n",
+            "    Album {\n",
+            "        integer id\n",
+            "        string title\n",
+            "    }\n",
+            "    Artist {\n",
+            "        string name\n",
+            "    }\n",
+            "    %% Entities end\n",
+            "    %% Relationships start\n",
+            "    Album }o--|| Artist : \"artist\"\n",
+            "    Album }o--o{ Artist : \"featuring\"\n",
+            "    %% Relationships end",
+        );
+        // act
+        let erd = ERD::from_json_schema(SCHEMA).expect("Expected a valid schema");
+        // assert
+        crate::assert_mermaid_eq!(erd.to_string(), wanted);
+        let album = erd
+            .get_entity_by_id(&EntityId::from("Album"))
+            .expect("Expected entity");
+        let id = album.attribute_by_name("id").expect("Expected attribute");
+        assert_eq!(
+            id.metadata.get("nullable").map(String::as_str),
+            Some("false")
+        );
+    }
+
+    #[test]
+    fn missing_ref_should_error() {
+        // arrange
+        let schema =
+            r##"{"$defs": {"A": {"type": "object", "properties": {"b": {"$ref": "#/$defs/B"}}}}}"##;
+        // act
+        let got = ERD::from_json_schema(schema).map(|erd| erd.entity_count());
+        // assert
+        assert_eq!(
+            got,
+            Err(ParseError::new(
+                0,
+                "#/$defs/B isn't found in the definitions"
+            ))
+        );
+    }
+
+    #[test]
+    fn ref_to_non_object_definition_becomes_an_attribute() {
+        // arrange
+        let schema = r##"{"$defs": {
+            "A": {"type": "object", "properties": {"b": {"$ref": "#/$defs/Code"}}},
+            "Code": {"type": "string"}
+        }}"##;
+        let wanted = concat!(
+            "erDiagram\n",
+            "    %% Entities start\n",
+            "    A {\n",
+            "        string b\n",
+            "    }\n",
+            "    %% Entities end",
+        );
+        // act
+        let got = ERD::from_json_schema(schema).expect("Expected a valid schema");
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn external_ref_should_error() {
+        // arrange
+        let schema = r#"{"$defs": {
+            "A": {"type": "object", "properties": {"b": {"$ref": "other.json#/definitions/A"}}}
+        }}"#;
+        // act
+        let got = ERD::from_json_schema(schema).map(|erd| erd.entity_count());
+        // assert
+        assert_eq!(
+            got,
+            Err(ParseError::new(
+                0,
+                "other.json#/definitions/A isn't a local reference to a definition"
+            ))
+        );
+    }
+
+    #[test]
+    fn attributes_keep_the_order_of_the_properties() {
+        // arrange
+        let schema = r#"{"$defs": {"A": {"type": "object", "properties": {
+            "zeta": {"type": "string"},
+            "alpha": {"type": "integer"}
+        }}}}"#;
+        let wanted = concat!(
+            "erDiagram\n",
+            "    %% Entities start\n",
+            "    A {\n",
+            "        string zeta\n",
+            "        integer alpha\n",
+            "    }\n",
+            "    %% Entities end",
+        );
+        // act
+        let got = ERD::from_json_schema(schema).expect("Expected a valid schema");
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn invalid_json_should_error() {
+        // act
+        let got = ERD::from_json_schema("{\n  \"$defs\": ").map(|erd| erd.entity_count());
+        // assert
+        assert!(matches!(got, Err(ParseError { line: 2, .. })));
+    }
+}
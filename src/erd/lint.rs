@@ -0,0 +1,170 @@
+// ==================================================================
+// Implement ERD methods to check the diagram for common mistakes
+// ==================================================================
+
+use std::collections::HashSet;
+
+use super::{Entity, ERD};
+use crate::utils::sanitize_identifier;
+use crate::MormaidError;
+
+impl ERD {
+    /// Run every check on the ERD and collect all of the issues it finds.
+    ///
+    /// The checks report:
+    /// - entity ids that mermaid can't render ([`MormaidError::InvalidEntityId`])
+    /// - attributes defined more than once in an entity ([`MormaidError::DuplicateAttribute`])
+    /// - entities without relationships ([`MormaidError::IsolatedEntity`])
+    /// - relationship endpoints that only differ from another entity's id by case,
+    ///   which usually means a typo created a new entity ([`MormaidError::CaseMismatch`])
+    /// - foreign keys on entities without relationships ([`MormaidError::UnrelatedForeignKey`])
+    ///
+    /// Issues are grouped by check and listed in the order entities are rendered.
+    /// An empty list means the ERD passed every check.
+    #[must_use]
+    pub fn lint(&self) -> Vec<MormaidError> {
+        let entities: Vec<&Entity> = self.iter().collect();
+        let mut issues = Vec::new();
+        issues.extend(entities.iter().filter_map(|e| invalid_id(e)));
+        issues.extend(entities.iter().flat_map(|e| duplicate_attributes(e)));
+        issues.extend(
+            self.isolated_entities()
+                .into_iter()
+                .map(|e| MormaidError::IsolatedEntity(e.id.clone())),
+        );
+        issues.extend(self.case_mismatches(&entities));
+        issues.extend(self.unrelated_foreign_keys(&entities));
+        issues
+    }
+
+    // Find relationship endpoints that match another entity's id when ignoring case
+    fn case_mismatches(&self, entities: &[&Entity]) -> Vec<MormaidError> {
+        let mut endpoints: Vec<&str> = Vec::new();
+        for r in &self.relationships {
+            for id in [r.left_id.as_str(), r.right_id.as_str()] {
+                if !endpoints.contains(&id) {
+                    endpoints.push(id);
+                }
+            }
+        }
+        let mut issues = Vec::new();
+        for found in endpoints {
+            for entity in entities {
+                if entity.id != found && entity.id.eq_ignore_ascii_case(found) {
+                    issues.push(MormaidError::CaseMismatch {
+                        found: found.to_string(),
+                        expected: entity.id.clone(),
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    // Find foreign keys on entities that aren't in any relationships
+    fn unrelated_foreign_keys(&self, entities: &[&Entity]) -> Vec<MormaidError> {
+        let related: HashSet<&str> = self
+            .relationships
+            .iter()
+            .flat_map(|r| [r.left_id.as_str(), r.right_id.as_str()])
+            .collect();
+        entities
+            .iter()
+            .filter(|entity| !related.contains(entity.id.as_str()))
+            .flat_map(|entity| {
+                entity
+                    .attributes
+                    .iter()
+                    .filter(|attr| attr.key.is_foreign)
+                    .map(|attr| MormaidError::UnrelatedForeignKey {
+                        entity: entity.id.clone(),
+                        attribute: attr.name.clone(),
+                    })
+            })
+            .collect()
+    }
+}
+
+// Check that the id only has characters mermaid allows in an unquoted entity name
+fn invalid_id(entity: &Entity) -> Option<MormaidError> {
+    let is_valid = !entity.id.is_empty() && sanitize_identifier(&entity.id) == entity.id;
+    (!is_valid).then(|| MormaidError::InvalidEntityId(entity.id.clone()))
+}
+
+// Report each attribute name that's used more than once, the first time it repeats
+fn duplicate_attributes(entity: &Entity) -> Vec<MormaidError> {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    let mut issues = Vec::new();
+    for attr in &entity.attributes {
+        if !seen.insert(attr.name.as_str()) && reported.insert(attr.name.as_str()) {
+            issues.push(MormaidError::DuplicateAttribute {
+                entity: entity.id.clone(),
+                attribute: attr.name.clone(),
+            });
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn lint_clean_erd_has_no_issues() {
+        // arrange
+        let erd = ERD::new().with_relationship(Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        ));
+        // act
+        let got = erd.lint();
+        // assert
+        assert_eq!(got, Vec::new());
+    }
+
+    #[test]
+    fn lint_broken_erd_reports_every_issue() {
+        // arrange
+        let erd = ERD::new()
+            .with_entity(
+                Entity::new("ALBUM")
+                    .with_attribute(Attribute::new("int", "id").as_primary_key())
+                    .with_attribute(Attribute::new("int", "id")),
+            )
+            .with_entity(
+                Entity::new("MUSIC GENRE")
+                    .with_attribute(Attribute::new("int", "albumId").as_foreign_key()),
+            )
+            .with_relationship(Relationship::new(
+                "album",
+                "SONG",
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+        let wanted = vec![
+            MormaidError::InvalidEntityId("MUSIC GENRE".to_string()),
+            MormaidError::DuplicateAttribute {
+                entity: "ALBUM".to_string(),
+                attribute: "id".to_string(),
+            },
+            MormaidError::IsolatedEntity("ALBUM".to_string()),
+            MormaidError::IsolatedEntity("MUSIC GENRE".to_string()),
+            MormaidError::CaseMismatch {
+                found: "album".to_string(),
+                expected: "ALBUM".to_string(),
+            },
+            MormaidError::UnrelatedForeignKey {
+                entity: "MUSIC GENRE".to_string(),
+                attribute: "albumId".to_string(),
+            },
+        ];
+        // act
+        let got = erd.lint();
+        // assert
+        assert_eq!(got, wanted);
+    }
+}
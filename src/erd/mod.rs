@@ -1,12 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 
 pub mod entity;
+mod export;
+#[cfg(feature = "json")]
+mod json_schema;
+mod lint;
 pub mod relationship;
 
-use crate::utils;
-pub use entity::{Attribute, Entity};
-pub use relationship::{Cardinality, Relationship};
+use crate::render::RenderOptions;
+use crate::{utils, MormaidError};
+pub use entity::{Attribute, Entity, Stereotype, TypeParams};
+pub use relationship::{Cardinality, Direction, Relationship};
 
 // ==================================================================
 // EntityId struct and implementation
@@ -14,6 +19,7 @@ pub use relationship::{Cardinality, Relationship};
 
 #[must_use]
 #[derive(Hash, Eq, PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityId(String);
 
 impl EntityId {
@@ -32,22 +38,93 @@ impl From<&str> for EntityId {
         EntityId(s.to_string())
     }
 }
+
+impl From<&Entity> for EntityId {
+    fn from(entity: &Entity) -> Self {
+        EntityId::from(entity.id.as_str())
+    }
+}
+
+impl From<Entity> for EntityId {
+    fn from(entity: Entity) -> Self {
+        EntityId(entity.id)
+    }
+}
 // ================================================================
 // ERD struct and implementation
 // ================================================================
 #[must_use]
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ERD {
     pub title: Option<String>,
     pub entities: HashMap<EntityId, Entity>,
     pub relationships: Vec<Relationship>,
+    /// Reject relationships that reference an entity missing from the ERD
+    /// instead of creating the entity. See [`ERD::new_strict()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_strict: bool,
+    /// Explain the cardinality symbols used in the ERD with a legend of comments.
+    /// See [`ERD::with_legend()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_legend: bool,
+    /// The accessible title read by screen readers, see [`ERD::with_acc_title()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub acc_title: Option<String>,
+    /// The accessible description read by screen readers, see [`ERD::with_acc_descr()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub acc_descr: Option<String>,
 }
 impl ERD {
+    /// The keyword that starts every mermaid ERD.
+    pub const DIAGRAM_KEYWORD: &'static str = "erDiagram";
+
     pub fn new() -> Self {
         ERD {
             title: None,
             entities: HashMap::new(),
             relationships: Vec::new(),
+            is_strict: false,
+            show_legend: false,
+            acc_title: None,
+            acc_descr: None,
+        }
+    }
+
+    /// Chain with [`ERD::new()`] to add an accessible title for screen readers.
+    ///
+    /// The title is rendered as an `accTitle:` line right after the `erDiagram` keyword.
+    pub fn with_acc_title(mut self, title: &str) -> Self {
+        self.acc_title = Some(title.to_string());
+        self
+    }
+
+    /// Chain with [`ERD::new()`] to add an accessible description for screen readers.
+    ///
+    /// The description is rendered as an `accDescr:` line after the `erDiagram`
+    /// keyword (and the `accTitle`, if there is one), or as an `accDescr { ... }`
+    /// block if it spans several lines.
+    pub fn with_acc_descr(mut self, descr: &str) -> Self {
+        self.acc_descr = Some(descr.to_string());
+        self
+    }
+
+    /// Chain with [`ERD::new()`] to append a legend explaining the cardinality symbols.
+    ///
+    /// The legend is rendered as `%%` comments after the relationships, so it's
+    /// only visible in the diagram source. It only lists the cardinalities used
+    /// by the ERD's relationships.
+    pub fn with_legend(mut self) -> Self {
+        self.show_legend = true;
+        self
+    }
+
+    /// Create an ERD in strict mode, in which [`ERD::add_relationship()`] panics
+    /// instead of creating the entities a relationship references.
+    pub fn new_strict() -> Self {
+        ERD {
+            is_strict: true,
+            ..ERD::new()
         }
     }
 }
@@ -55,19 +132,147 @@ impl ERD {
 // implement the Display trait
 impl fmt::Display for ERD {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render_with(&RenderOptions::default()))
+    }
+}
+
+impl ERD {
+    /// Render the ERD to mermaid syntax using the options provided.
+    #[must_use]
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        self.render_sections(self.entity_blocks(options), options)
+    }
+
+    /// Render the ERD with connected entities next to each other.
+    ///
+    /// The entities are ordered by a breadth-first walk of the relationships,
+    /// starting from the most connected entity (ties go to the lowest id). Each
+    /// entity's neighbors are visited in id order, and entities that can't be
+    /// reached are walked the same way afterwards. The relationships are rendered
+    /// the same as [`ERD::to_string()`].
+    #[must_use]
+    pub fn render_clustered(&self) -> String {
+        let options = RenderOptions::default();
+        let blocks = self
+            .clustered_entities()
+            .into_iter()
+            .map(|entity| entity.render(&options))
+            .collect();
+        self.render_sections(blocks, &options)
+    }
+
+    // Render the keyword followed by each section of the ERD
+    fn render_sections(&self, entity_blocks: Vec<String>, options: &RenderOptions) -> String {
         // initialize the erDiagram
-        let mut erd_str = "erDiagram".to_string();
+        let mut erd_str = options.keyword_or(Self::DIAGRAM_KEYWORD).to_string();
+        erd_str = utils::append_accessibility(
+            erd_str,
+            self.acc_title.as_deref(),
+            self.acc_descr.as_deref(),
+        );
 
         // append entities if the ERD has them
-        if !self.entities.is_empty() {
-            erd_str = utils::append_items(erd_str, self.entities.values(), "Entities", 4);
+        if !entity_blocks.is_empty() {
+            erd_str = utils::append_items_with(erd_str, entity_blocks, "Entities", 4, options);
         }
 
         // append relationships if the ERD has them
         if !self.relationships.is_empty() {
-            erd_str = utils::append_items(erd_str, &self.relationships, "Relationships", 4);
+            let blocks = self.relationship_blocks(options);
+            erd_str = utils::append_items_with(erd_str, blocks, "Relationships", 4, options);
+        }
+
+        // append the legend if it's enabled and there are symbols to explain
+        if self.show_legend {
+            let legend = self.legend_lines();
+            if !legend.is_empty() {
+                erd_str = utils::append_items_with(erd_str, legend, "Legend", 4, options);
+            }
         }
-        write!(f, "{erd_str}")
+        options.line_ending.apply(&erd_str)
+    }
+
+    // Order the entities with a breadth-first walk from the most connected one
+    fn clustered_entities(&self) -> Vec<&Entity> {
+        let mut ids: Vec<&EntityId> = self.entities.keys().collect();
+        // sort by the number of relationships, most first, then by id
+        ids.sort_by(|a, b| {
+            let degree = |id: &EntityId| self.relationships_for(id).len();
+            degree(b)
+                .cmp(&degree(a))
+                .then_with(|| a.as_str().cmp(b.as_str()))
+        });
+        let mut visited: HashSet<&EntityId> = HashSet::new();
+        let mut ordered = Vec::new();
+        for start in ids {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut queue = std::collections::VecDeque::from([start]);
+            while let Some(id) = queue.pop_front() {
+                ordered.push(&self.entities[id]);
+                let mut neighbors: Vec<&EntityId> = self
+                    .relationships_for(id)
+                    .into_iter()
+                    .map(|r| {
+                        if &r.left_id == id {
+                            &r.right_id
+                        } else {
+                            &r.left_id
+                        }
+                    })
+                    .collect();
+                neighbors.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+                for neighbor in neighbors {
+                    if self.entities.contains_key(neighbor) && visited.insert(neighbor) {
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+        ordered
+    }
+
+    // Explain each cardinality used by a relationship, in a fixed order
+    fn legend_lines(&self) -> Vec<String> {
+        Cardinality::all_variants()
+            .iter()
+            .filter(|&cardinality| {
+                self.relationships.iter().any(|r| {
+                    &r.left_cardinality == cardinality || &r.right_cardinality == cardinality
+                })
+            })
+            .map(|cardinality| {
+                format!(
+                    "%% {} or {} : {}",
+                    cardinality.symbol(Direction::Left),
+                    cardinality.symbol(Direction::Right),
+                    cardinality.describe(),
+                )
+            })
+            .collect()
+    }
+
+    /// Render the ERD with the default options, same as `to_string()`.
+    #[must_use]
+    pub fn to_pretty(&self) -> String {
+        self.render_with(&RenderOptions::default())
+    }
+
+    /// Render the ERD without the comments around each section.
+    #[must_use]
+    pub fn to_minified(&self) -> String {
+        self.render_with(&RenderOptions::new().with_compact(true))
+    }
+}
+
+// iterate over the entities in the same order they're rendered
+impl<'a> IntoIterator for &'a ERD {
+    type Item = &'a Entity;
+    type IntoIter = std::vec::IntoIter<&'a Entity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
@@ -87,16 +292,187 @@ impl ERD {
         self
     }
 
+    /// Add several entities to the ERD on creation by chaining with [`ERD::new()`].
+    pub fn with_entities(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        for entity in entities {
+            self.add_entity(entity);
+        }
+        self
+    }
+
+    /// Create an ERD from `(id, attributes)` pairs, with one entity per pair.
+    ///
+    /// This is shorthand for [`ERD::with_entity()`] with an [`Entity`] built from
+    /// each id and its attributes, in order. A later pair with the same id
+    /// replaces the earlier entity.
+    pub fn from_tables<'a>(tables: impl IntoIterator<Item = (&'a str, Vec<Attribute>)>) -> ERD {
+        ERD::new().with_entities(tables.into_iter().map(|(id, attributes)| {
+            let mut entity = Entity::new(id);
+            entity.attributes = attributes;
+            entity
+        }))
+    }
+
+    /// The number of entities in the ERD, including ones created by relationships.
+    #[must_use]
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Check if the ERD has no entities and no relationships.
+    ///
+    /// The title and render flags (e.g. `show_legend`) aren't content, so an ERD
+    /// with only a title is still empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.relationships.is_empty()
+    }
+
+    /// Collapse duplicate attributes in every entity, e.g. after merging ERDs.
+    ///
+    /// Attributes are duplicates if they share a name, see
+    /// [`Entity::dedupe_attributes()`] for how their definitions are merged.
+    pub fn dedupe_attributes(&mut self) {
+        for entity in self.entities.values_mut() {
+            entity.dedupe_attributes();
+        }
+    }
+
+    /// Check if an entity with this id exists in the ERD.
+    #[must_use]
+    pub fn contains_entity(&self, id: &EntityId) -> bool {
+        self.entities.contains_key(id)
+    }
+
+    /// List every distinct attribute type used by the entities in the ERD, sorted.
+    #[must_use]
+    pub fn attribute_types(&self) -> BTreeSet<String> {
+        self.entities
+            .values()
+            .flat_map(|entity| entity.attributes.iter())
+            .map(|attr| attr.attr_type.clone())
+            .collect()
+    }
+
     /// Try to find an entity in the ERD using its id.
     #[must_use]
     pub fn get_entity_by_id(&self, id: &EntityId) -> Option<&Entity> {
         self.entities.get(id)
     }
 
+    // Format the relationships, optionally grouping them by their left entity
+    fn relationship_blocks(&self, options: &RenderOptions) -> Vec<String> {
+        // pad the left ids to the widest one so the glyphs line up, if requested
+        let width = if options.align_relationships {
+            self.relationships
+                .iter()
+                .map(|r| r.left_id.as_str().chars().count())
+                .max()
+                .unwrap_or_default()
+        } else {
+            0
+        };
+        if !options.group_relationships {
+            return self
+                .relationships
+                .iter()
+                .map(|r| r.render_padded(options, width))
+                .collect();
+        }
+        // sort by left entity, keeping the insertion order within each group
+        let mut relationships: Vec<&Relationship> = self.relationships.iter().collect();
+        relationships.sort_by(|a, b| a.left_id.as_str().cmp(b.left_id.as_str()));
+        let mut blocks: Vec<String> = Vec::new();
+        let mut group_id: Option<&EntityId> = None;
+        for r in relationships {
+            if group_id != Some(&r.left_id) && !options.compact {
+                blocks.push(format!("%% relationships from {}", r.left_id.as_str()));
+            }
+            group_id = Some(&r.left_id);
+            blocks.push(r.render_padded(options, width));
+        }
+        blocks
+    }
+
+    /// Iterate over the entities in the ERD, sorted by namespace then by id.
+    ///
+    /// Entities without a namespace come first. This is the same order in
+    /// which the entities are rendered.
+    #[must_use]
+    pub fn iter(&self) -> std::vec::IntoIter<&Entity> {
+        let mut entities: Vec<&Entity> = self.entities.values().collect();
+        entities.sort_by(|a, b| (&a.namespace, &a.id).cmp(&(&b.namespace, &b.id)));
+        entities.into_iter()
+    }
+
+    /// The entities in the ERD in the same deterministic order as [`ERD::iter()`].
+    ///
+    /// Prefer this over the `entities` field, whose `HashMap` iterates in an
+    /// arbitrary order. The iterator's length is the number of entities.
+    #[must_use]
+    pub fn entities(&self) -> impl ExactSizeIterator<Item = &Entity> {
+        self.iter()
+    }
+
+    // Format the entities, grouping the ones that share a namespace together
+    fn entity_blocks(&self, options: &RenderOptions) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut entities = self.iter().peekable();
+        while let Some(entity) = entities.next() {
+            let Some(namespace) = entity.namespace.as_deref() else {
+                blocks.push(entity.render(options));
+                continue;
+            };
+            // collect the rest of the entities in the same namespace
+            let mut group = vec![entity.render(options)];
+            while let Some(next) = entities.next_if(|e| e.namespace == entity.namespace) {
+                group.push(next.render(options));
+            }
+            let note = format!("Namespace {namespace}");
+            let block = utils::append_items_with(String::new(), group, &note, 0, options);
+            blocks.push(block.trim_start().to_string());
+        }
+        blocks
+    }
+
+    /// Rename an entity and update every relationship that references it.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingEntity`] if no entity has the old id, or
+    /// [`MormaidError::DuplicateEntity`] if an entity with the new id already
+    /// exists. In both cases the ERD is unchanged.
+    pub fn rename_entity(&mut self, old: &EntityId, new: &str) -> Result<(), MormaidError> {
+        let new_id = EntityId::from(new);
+        if self.entities.contains_key(&new_id) {
+            return Err(MormaidError::DuplicateEntity(new.to_string()));
+        }
+        // re-key the entity under its new id
+        let Some(mut entity) = self.entities.remove(old) else {
+            return Err(MormaidError::MissingEntity(old.as_str().to_string()));
+        };
+        entity.id = new.to_string();
+        self.entities.insert(new_id.clone(), entity);
+        // point the relationships at the new id
+        for r in &mut self.relationships {
+            if &r.left_id == old {
+                r.left_id = new_id.clone();
+            }
+            if &r.right_id == old {
+                r.right_id = new_id.clone();
+            }
+        }
+        Ok(())
+    }
+
     /// If a entity doesn't exist in the ERD, create and insert it.
+    ///
+    /// The new entity is flagged with `was_auto_created`, which can be rendered
+    /// with [`RenderOptions::with_mark_auto_created()`].
     pub fn create_entity_if_missing(&mut self, id: &EntityId) {
         if self.get_entity_by_id(id).is_none() {
-            self.add_entity(Entity::new(id.as_str()));
+            let mut entity = Entity::new(id.as_str());
+            entity.was_auto_created = true;
+            self.add_entity(entity);
         }
     }
 }
@@ -108,8 +484,19 @@ impl ERD {
     /// Add a relationship to `ERD.relationships`.
     ///
     /// This method also creates and adds the entities referenced in the relationship
-    /// if they don't already exist in `ERD.entities`.
+    /// if they don't already exist in `ERD.entities`, unless the ERD was created
+    /// with [`ERD::new_strict()`].
+    ///
+    /// # Panics
+    /// In strict mode this method panics if either the left or right entity isn't
+    /// found in the ERD. Use [`ERD::add_relationship_strict()`] to get an error instead.
     pub fn add_relationship(&mut self, relationship: Relationship) {
+        if self.is_strict {
+            if let Err(err) = self.add_relationship_strict(relationship) {
+                panic!("{err}");
+            }
+            return;
+        }
         // Ensure that both the left and right entities exist in the ERD
         self.create_entity_if_missing(&relationship.left_id);
         self.create_entity_if_missing(&relationship.right_id);
@@ -117,17 +504,228 @@ impl ERD {
         self.relationships.push(relationship);
     }
 
+    /// Add a relationship to `ERD.relationships` without creating missing entities.
+    ///
+    /// Unlike [`ERD::add_relationship()`], this method requires that both of the
+    /// entities referenced in the relationship already exist in `ERD.entities`,
+    /// which prevents a misspelled id from silently becoming a new entity.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingEntity`] if either the left or right entity
+    /// isn't found in the ERD, in which case the relationship isn't added.
+    pub fn add_relationship_strict(
+        &mut self,
+        relationship: Relationship,
+    ) -> Result<(), MormaidError> {
+        // Ensure that both the left and right entities exist in the ERD
+        for id in [&relationship.left_id, &relationship.right_id] {
+            if self.get_entity_by_id(id).is_none() {
+                return Err(MormaidError::MissingEntity(id.as_str().to_string()));
+            }
+        }
+        // Then add the relationship to the ERD
+        self.relationships.push(relationship);
+        Ok(())
+    }
+
     /// Add a relationship to the ERD on creation by chaining with [`ERD::new()`].
     pub fn with_relationship(mut self, relationship: Relationship) -> Self {
         self.add_relationship(relationship);
         self
     }
+
+    /// Add several relationships to the ERD on creation by chaining with [`ERD::new()`].
+    ///
+    /// Each relationship is added with [`ERD::add_relationship()`], so missing
+    /// entities are still created unless the ERD is strict.
+    pub fn with_relationships(
+        mut self,
+        relationships: impl IntoIterator<Item = Relationship>,
+    ) -> Self {
+        for relationship in relationships {
+            self.add_relationship(relationship);
+        }
+        self
+    }
+
+    /// List the relationships in which the entity is either the left or right entity.
+    #[must_use]
+    pub fn relationships_for(&self, id: &EntityId) -> Vec<&Relationship> {
+        self.relationships
+            .iter()
+            .filter(|r| &r.left_id == id || &r.right_id == id)
+            .collect()
+    }
+
+    /// The number of relationships in the ERD.
+    #[must_use]
+    pub fn relationship_count(&self) -> usize {
+        self.relationships.len()
+    }
+
+    /// List the entities that aren't referenced by any relationship in the ERD.
+    ///
+    /// Isolated entities often indicate that a diagram is incomplete. They're
+    /// returned in the same order that entities are rendered.
+    #[must_use]
+    pub fn isolated_entities(&self) -> Vec<&Entity> {
+        self.iter()
+            .filter(|entity| {
+                !self
+                    .relationships
+                    .iter()
+                    .any(|r| r.left_id.as_str() == entity.id || r.right_id.as_str() == entity.id)
+            })
+            .collect()
+    }
+
+    /// Check if any relationship connects the two entities.
+    ///
+    /// Direction is ignored, the same as [`Relationship::same_pair()`], so the
+    /// ids can be passed in either order.
+    #[must_use]
+    pub fn contains_relationship(&self, left: &EntityId, right: &EntityId) -> bool {
+        self.relationships.iter().any(|r| {
+            (&r.left_id == left && &r.right_id == right)
+                || (&r.left_id == right && &r.right_id == left)
+        })
+    }
+
+    /// Remove every relationship from the ERD while keeping its entities.
+    pub fn clear_relationships(&mut self) {
+        self.relationships.clear();
+    }
+
+    /// Add a relationship for each attribute that references another entity,
+    /// see [`Attribute::references()`].
+    ///
+    /// Each relationship goes from the referenced entity (exactly one) to the entity
+    /// with the attribute (zero or more), and is labeled with the attribute's name so
+    /// that attributes referencing the same entity get distinct relationships. It's
+    /// identifying if the attribute is also a primary key, and non-identifying
+    /// otherwise. A relationship with the same entities and label is only added once,
+    /// so this method can be called again after adding more attributes.
+    ///
+    /// # Panics
+    /// Like [`ERD::add_relationship()`], in strict mode this method panics if a
+    /// referenced entity isn't found in the ERD. Otherwise the missing entity is created.
+    pub fn add_reference_relationships(&mut self) {
+        let mut generated: Vec<Relationship> = Vec::new();
+        for entity in &*self {
+            for attr in &entity.attributes {
+                let Some(referenced) = attr.references.as_deref() else {
+                    continue;
+                };
+                let mut relationship = Relationship::new(
+                    referenced,
+                    &entity.id,
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                )
+                .with_label(&attr.name);
+                if !attr.key.is_primary {
+                    relationship = relationship.as_non_identifying();
+                }
+                generated.push(relationship);
+            }
+        }
+        for relationship in generated {
+            let exists = self.relationships.iter().any(|r| {
+                r.left_id == relationship.left_id
+                    && r.right_id == relationship.right_id
+                    && r.label == relationship.label
+            });
+            if !exists {
+                self.add_relationship(relationship);
+            }
+        }
+    }
+}
+
+// ==================================================
+// Implement ERD methods to render part of the diagram
+// ==================================================
+impl ERD {
+    /// Create a new ERD focused on the neighborhood around a single entity.
+    ///
+    /// The new ERD contains the center entity, every entity that can be reached
+    /// from it within `depth` relationships (in either direction), and the
+    /// relationships among those entities. The entities and relationships are
+    /// copied, so the original ERD is unchanged. If the center entity isn't
+    /// found in the ERD, the new ERD is empty.
+    pub fn subgraph(&self, center: &EntityId, depth: usize) -> ERD {
+        let mut subgraph = ERD::new();
+        subgraph.title.clone_from(&self.title);
+        if self.get_entity_by_id(center).is_none() {
+            return subgraph;
+        }
+        // walk outward from the center one relationship hop at a time
+        let mut visited: HashSet<&EntityId> = HashSet::from([center]);
+        let mut frontier = vec![center];
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for id in frontier {
+                for r in self.relationships_for(id) {
+                    let other = if &r.left_id == id {
+                        &r.right_id
+                    } else {
+                        &r.left_id
+                    };
+                    if visited.insert(other) {
+                        next.push(other);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        // copy the entities that were reached and the relationships among them
+        for id in &visited {
+            if let Some(entity) = self.get_entity_by_id(id) {
+                subgraph.add_entity(entity.clone());
+            }
+        }
+        for r in &self.relationships {
+            if visited.contains(&r.left_id) && visited.contains(&r.right_id) {
+                subgraph.relationships.push(r.clone());
+            }
+        }
+        subgraph
+    }
+
+    /// Render only the entities in one layer of a layered data model.
+    ///
+    /// Entities tagged with a different [`Stereotype`] are left out, along with
+    /// their relationships. Entities without a stereotype belong to every layer,
+    /// so they're always rendered. The ERD itself is unchanged.
+    #[must_use]
+    pub fn render_layer(&self, layer: Stereotype) -> String {
+        let mut erd = ERD::new();
+        erd.title.clone_from(&self.title);
+        erd.show_legend = self.show_legend;
+        erd.acc_title.clone_from(&self.acc_title);
+        erd.acc_descr.clone_from(&self.acc_descr);
+        for entity in self.entities.values() {
+            if entity
+                .stereotype
+                .is_none_or(|stereotype| stereotype == layer)
+            {
+                erd.add_entity(entity.clone());
+            }
+        }
+        for r in &self.relationships {
+            if erd.contains_entity(&r.left_id) && erd.contains_entity(&r.right_id) {
+                erd.relationships.push(r.clone());
+            }
+        }
+        erd.to_string()
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::render::LineEnding;
     const ALBUM_ID: &str = "ALBUM";
     const SONG_ID: &str = "SONG";
 
@@ -146,6 +744,152 @@ mod tests {
             // assert
             assert_eq!(first_id, second_id);
         }
+
+        #[test]
+        fn entity_id_from_entity() {
+            // arrange
+            let album = Entity::new(ALBUM_ID);
+            let erd = ERD::new().with_entity(album.clone());
+            // act
+            let from_ref = EntityId::from(&album);
+            let from_owned = EntityId::from(album);
+            // assert
+            assert_eq!(from_ref, from_owned);
+            assert!(erd.get_entity_by_id(&from_ref).is_some());
+        }
+    }
+
+    // =========================
+    // Subgraph tests
+    // =========================
+    mod subgraph_tests {
+
+        use super::*;
+
+        // ARTIST -- ALBUM -- SONG -- PLAY, plus an unconnected GENRE
+        fn chain() -> ERD {
+            let pairs = [("ARTIST", ALBUM_ID), (ALBUM_ID, SONG_ID), (SONG_ID, "PLAY")];
+            let mut erd = ERD::new().with_entity(Entity::new("GENRE"));
+            for (left, right) in pairs {
+                erd.add_relationship(Relationship::new(
+                    left,
+                    right,
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                ));
+            }
+            erd
+        }
+
+        fn entity_ids(erd: &ERD) -> Vec<&str> {
+            erd.iter().map(|entity| entity.id.as_str()).collect()
+        }
+
+        #[test]
+        fn subgraph_with_depth_zero_only_has_center() {
+            // act
+            let got = chain().subgraph(&EntityId::from(ALBUM_ID), 0);
+            // assert
+            assert_eq!(entity_ids(&got), vec![ALBUM_ID]);
+            assert!(got.relationships.is_empty());
+        }
+
+        #[test]
+        fn subgraph_with_depth_one_has_neighbors() {
+            // act
+            let got = chain().subgraph(&EntityId::from(ALBUM_ID), 1);
+            // assert
+            assert_eq!(entity_ids(&got), vec![ALBUM_ID, "ARTIST", SONG_ID]);
+            assert_eq!(got.relationships.len(), 2);
+        }
+
+        #[test]
+        fn subgraph_with_depth_two_has_neighbors_of_neighbors() {
+            // act
+            let got = chain().subgraph(&EntityId::from("ARTIST"), 2);
+            // assert
+            assert_eq!(entity_ids(&got), vec![ALBUM_ID, "ARTIST", SONG_ID]);
+            assert_eq!(got.relationships.len(), 2);
+            assert!(got.to_string().contains("ALBUM ||--o{ SONG"));
+        }
+
+        #[test]
+        fn subgraph_around_missing_entity_is_empty() {
+            // act
+            let got = chain().subgraph(&EntityId::from("FAKE"), 2);
+            // assert
+            assert!(got.entities.is_empty());
+            assert!(got.relationships.is_empty());
+        }
+
+        // conceptual ARTIST, logical ALBUM, physical SONG, and untagged GENRE
+        fn layered() -> ERD {
+            let pairs = [
+                ("ARTIST", ALBUM_ID),
+                (ALBUM_ID, SONG_ID),
+                ("GENRE", ALBUM_ID),
+            ];
+            let mut erd = ERD::new()
+                .with_entity(Entity::new("ARTIST").with_stereotype(Stereotype::Conceptual))
+                .with_entity(Entity::new(ALBUM_ID).with_stereotype(Stereotype::Logical))
+                .with_entity(Entity::new(SONG_ID).with_stereotype(Stereotype::Physical))
+                .with_entity(Entity::new("GENRE"));
+            for (left, right) in pairs {
+                erd.add_relationship(Relationship::new(
+                    left,
+                    right,
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                ));
+            }
+            erd
+        }
+
+        #[test]
+        fn render_each_layer_with_untagged_entities() {
+            // arrange
+            let layers = [
+                (
+                    Stereotype::Conceptual,
+                    concat!(
+                        "erDiagram\n",
+                        "    %% Entities start\n",
+                        "    ARTIST\n",
+                        "    GENRE\n",
+                        "    %% Entities end",
+                    ),
+                ),
+                (
+                    Stereotype::Logical,
+                    concat!(
+                        "erDiagram\n",
+                        "    %% Entities start\n",
+                        "    ALBUM\n",
+                        "    GENRE\n",
+                        "    %% Entities end\n",
+                        "    %% Relationships start\n",
+                        "    GENRE ||--o{ ALBUM : \"\"\n",
+                        "    %% Relationships end",
+                    ),
+                ),
+                (
+                    Stereotype::Physical,
+                    concat!(
+                        "erDiagram\n",
+                        "    %% Entities start\n",
+                        "    GENRE\n",
+                        "    SONG\n",
+                        "    %% Entities end",
+                    ),
+                ),
+            ];
+            for (layer, wanted) in layers {
+                // act
+                let got = layered().render_layer(layer);
+                // assert
+                crate::assert_mermaid_eq!(got, wanted);
+            }
+        }
     }
 
     mod erd_tests {
@@ -222,6 +966,43 @@ mod tests {
             assert_eq!(entity_count_new, entity_count_old + 2);
         }
 
+        #[test]
+        fn add_strict_relationship_for_existing_entities() {
+            // arrange
+            let mut erd = ERD::new()
+                .with_entity(Entity::new(ALBUM_ID))
+                .with_entity(Entity::new(SONG_ID));
+            // act
+            let got = erd.add_relationship_strict(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            // assert
+            assert_eq!(got, Ok(()));
+            assert_eq!(erd.relationships.len(), 1);
+            assert_eq!(erd.entities.len(), 2);
+        }
+
+        #[test]
+        // Strict insertion should error instead of creating the missing entity
+        fn add_strict_relationship_for_missing_entity() {
+            // arrange
+            let mut erd = ERD::new().with_entity(Entity::new(ALBUM_ID));
+            // act
+            let got = erd.add_relationship_strict(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            // assert
+            assert_eq!(got, Err(MormaidError::MissingEntity(SONG_ID.to_string())));
+            assert!(erd.relationships.is_empty());
+            assert_eq!(erd.entities.len(), 1);
+        }
+
         #[test]
         fn create_erd_with_relationship() {
             // act
@@ -237,41 +1018,783 @@ mod tests {
         }
 
         #[test]
-        fn display_empty_diagram() {
+        fn iterate_over_each_entity_once() {
             // arrange
-            let wanted = "erDiagram";
-            let erd = ERD::new();
+            let erd = ERD::new()
+                .with_entity(Entity::new(SONG_ID))
+                .with_entity(Entity::new(ALBUM_ID))
+                .with_entity(Entity::new("ARTIST"));
             // act
-            let got = erd.to_string();
+            let mut got = Vec::new();
+            for entity in &erd {
+                got.push(entity.id.as_str());
+            }
             // assert
-            assert_eq!(got, wanted);
+            assert_eq!(got, vec![ALBUM_ID, "ARTIST", SONG_ID]);
         }
 
         #[test]
-        fn display_erd_with_entities_and_their_attributes() {
+        fn build_erd_from_collections() {
             // arrange
-            let attr_type = "string";
-            let erd = ERD::new()
-                .with_entity(
-                    Entity::new(ALBUM_ID)
-                        .with_attribute(Attribute::new(attr_type, "foo"))
-                        .with_attribute(Attribute::new(attr_type, "bar")),
-                )
-                .with_entity(Entity::new(SONG_ID));
-            let album_wanted = concat!(
-                "    ALBUM {\n",
-                "        string foo\n",
-                "        string bar\n",
-                "    }",
-            );
-            let song_wanted = "SONG";
+            let entities = vec![Entity::new(ALBUM_ID), Entity::new(SONG_ID)];
+            let relationships = vec![
+                Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ),
+                Relationship::new(
+                    "ARTIST",
+                    ALBUM_ID,
+                    Cardinality::OneOrMore,
+                    Cardinality::OneOrMore,
+                ),
+            ];
             // act
-            let got = erd.to_string();
+            let erd = ERD::new()
+                .with_entities(entities)
+                .with_relationships(relationships);
             // assert
-            assert!(got.contains(album_wanted));
+            assert_eq!(erd.entity_count(), 3);
+            assert_eq!(erd.relationship_count(), 2);
+            assert!(erd.contains_entity(&EntityId::from("ARTIST")));
+        }
+
+        #[test]
+        fn create_erd_from_tables() {
+            // arrange
+            let tables = [
+                (
+                    ALBUM_ID,
+                    vec![
+                        Attribute::new("int", "albumId").as_primary_key(),
+                        Attribute::new("string", "title"),
+                    ],
+                ),
+                (
+                    SONG_ID,
+                    vec![Attribute::new("int", "songId").as_primary_key()],
+                ),
+            ];
+            // act
+            let erd = ERD::from_tables(tables);
+            // assert
+            assert_eq!(erd.entity_count(), 2);
+            let album = erd
+                .get_entity_by_id(&EntityId::from(ALBUM_ID))
+                .expect("Expected entity");
+            let song = erd
+                .get_entity_by_id(&EntityId::from(SONG_ID))
+                .expect("Expected entity");
+            assert_eq!(album.attributes.len(), 2);
+            assert_eq!(song.attributes.len(), 1);
+            assert!(album.attributes[0].is_primary());
+        }
+
+        #[test]
+        fn lenient_erd_creates_missing_entities() {
+            // arrange
+            let mut erd = ERD::new().with_entity(Entity::new(ALBUM_ID));
+            // act
+            erd.add_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            // assert
+            assert!(!erd.is_strict);
+            assert!(erd.get_entity_by_id(&EntityId::from(SONG_ID)).is_some());
+            assert_eq!(erd.relationship_count(), 1);
+        }
+
+        #[test]
+        #[should_panic = "SONG isn't found in the list of entities"]
+        fn strict_erd_panics_on_missing_entity() {
+            // arrange
+            let mut erd = ERD::new_strict().with_entity(Entity::new(ALBUM_ID));
+            // act
+            erd.add_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+        }
+
+        #[test]
+        fn strict_erd_adds_relationship_between_existing_entities() {
+            // arrange
+            let mut erd = ERD::new_strict()
+                .with_entity(Entity::new(ALBUM_ID))
+                .with_entity(Entity::new(SONG_ID));
+            // act
+            erd.add_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            // assert
+            assert_eq!(erd.relationship_count(), 1);
+            assert_eq!(erd.entity_count(), 2);
+        }
+
+        #[test]
+        fn rename_entity_in_two_relationships() {
+            // arrange
+            let mut erd = ERD::new()
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ))
+                .with_relationship(Relationship::new(
+                    "ARTIST",
+                    ALBUM_ID,
+                    Cardinality::OneOrMore,
+                    Cardinality::OneOrMore,
+                ));
+            // act
+            let got = erd.rename_entity(&EntityId::from(ALBUM_ID), "RECORD");
+            // assert
+            assert_eq!(got, Ok(()));
+            let record_id = EntityId::from("RECORD");
+            let record = erd.get_entity_by_id(&record_id).expect("Expected entity");
+            assert_eq!(record.id, "RECORD");
+            assert!(erd.get_entity_by_id(&EntityId::from(ALBUM_ID)).is_none());
+            assert_eq!(erd.relationships[0].left_id, record_id);
+            assert_eq!(erd.relationships[1].right_id, record_id);
+        }
+
+        #[test]
+        fn rename_entity_to_existing_id_should_error() {
+            // arrange
+            let mut erd = ERD::new()
+                .with_entity(Entity::new(ALBUM_ID))
+                .with_entity(Entity::new(SONG_ID));
+            // act
+            let got = erd.rename_entity(&EntityId::from(ALBUM_ID), SONG_ID);
+            // assert
+            assert_eq!(got, Err(MormaidError::DuplicateEntity(SONG_ID.to_string())));
+            assert!(erd.get_entity_by_id(&EntityId::from(ALBUM_ID)).is_some());
+        }
+
+        #[test]
+        fn rename_missing_entity_should_error() {
+            // arrange
+            let mut erd = ERD::new();
+            // act
+            let got = erd.rename_entity(&EntityId::from(ALBUM_ID), SONG_ID);
+            // assert
+            assert_eq!(got, Err(MormaidError::MissingEntity(ALBUM_ID.to_string())));
+        }
+
+        #[test]
+        fn contains_entity_and_relationship() {
+            // arrange
+            let album = EntityId::from(ALBUM_ID);
+            let song = EntityId::from(SONG_ID);
+            let artist = EntityId::from("ARTIST");
+            let erd = ERD::new()
+                .with_entity(Entity::new("ARTIST"))
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ));
+            // act & assert
+            assert!(erd.contains_entity(&album));
+            assert!(erd.contains_entity(&artist));
+            assert!(!erd.contains_entity(&EntityId::from("GENRE")));
+            assert!(erd.contains_relationship(&album, &song));
+            assert!(erd.contains_relationship(&song, &album));
+            assert!(!erd.contains_relationship(&album, &artist));
+        }
+
+        #[test]
+        fn dedupe_attributes_merges_constraints_and_comment() {
+            // arrange
+            let mut erd = ERD::new().with_entity(
+                Entity::new(ALBUM_ID)
+                    .with_attribute(Attribute::new("int", "id").as_primary_key())
+                    .with_attribute(Attribute::new("string", "title"))
+                    .with_attribute(Attribute::new("int", "id").with_comment("Album id")),
+            );
+            // act
+            erd.dedupe_attributes();
+            // assert
+            let album = erd
+                .get_entity_by_id(&EntityId::from(ALBUM_ID))
+                .expect("Expected entity");
+            assert_eq!(album.attributes.len(), 2);
+            let id = &album.attributes[0];
+            assert_eq!(id.name, "id");
+            assert!(id.key.is_primary);
+            assert_eq!(id.comment.as_deref(), Some("Album id"));
+            assert_eq!(id.to_string(), "int id PK \"Album id\"");
+            assert_eq!(album.attributes[1].name, "title");
+        }
+
+        #[test]
+        fn mark_only_auto_created_entities() {
+            // arrange
+            let erd = ERD::new()
+                .with_entity(Entity::new(ALBUM_ID))
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ));
+            let options = RenderOptions::new()
+                .with_compact(true)
+                .with_mark_auto_created(true);
+            let wanted = concat!(
+                "erDiagram\n",
+                "    ALBUM\n",
+                "    %% auto-created\n",
+                "    SONG\n",
+                "    ALBUM ||--|{ SONG : \"\"",
+            );
+            // act
+            let got = erd.render_with(&options);
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+            assert!(!erd.to_string().contains("auto-created"));
+        }
+
+        #[test]
+        fn render_clustered_places_connected_entities_together() {
+            // arrange
+            let rel = |left: &str, right: &str| {
+                Relationship::new(left, right, Cardinality::ExactlyOne, Cardinality::OneOrMore)
+            };
+            // the path A-D-B-C starts from B, the most connected entity with the lowest id
+            let erd = ERD::new()
+                .with_relationship(rel("A", "D"))
+                .with_relationship(rel("B", "C"))
+                .with_relationship(rel("D", "B"));
+            let wanted = concat!(
+                "erDiagram\n",
+                "    %% Entities start\n",
+                "    B\n",
+                "    C\n",
+                "    D\n",
+                "    A\n",
+                "    %% Entities end\n",
+                "    %% Relationships start\n",
+                "    A ||--|{ D : \"\"\n",
+                "    B ||--|{ C : \"\"\n",
+                "    D ||--|{ B : \"\"\n",
+                "    %% Relationships end",
+            );
+            // act
+            let got = erd.render_clustered();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn legend_lists_only_used_cardinalities() {
+            // arrange
+            let erd = ERD::new()
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ))
+                .with_legend();
+            let wanted = concat!(
+                "    %% Legend start\n",
+                "    %% || or || : exactly one\n",
+                "    %% }| or |{ : one or more\n",
+                "    %% Legend end",
+            );
+            // act
+            let got = erd.to_string();
+            // assert
+            assert!(
+                got.ends_with(wanted),
+                "legend not found at the end of:\n{got}"
+            );
+            assert!(!got.contains("zero or"));
+            assert!(!ERD::new().with_legend().to_string().contains("Legend"));
+        }
+
+        #[test]
+        fn list_distinct_attribute_types() {
+            // arrange
+            let erd = ERD::new()
+                .with_entity(
+                    Entity::new(ALBUM_ID)
+                        .with_attribute(Attribute::new("string", "title"))
+                        .with_attribute(Attribute::new("int", "year")),
+                )
+                .with_entity(
+                    Entity::new(SONG_ID)
+                        .with_attribute(Attribute::new("string", "name"))
+                        .with_attribute(Attribute::new("float", "length")),
+                )
+                .with_entity(Entity::new("ARTIST"));
+            let wanted: BTreeSet<String> = ["float", "int", "string"]
+                .into_iter()
+                .map(String::from)
+                .collect();
+            // act
+            let got = erd.attribute_types();
+            // assert
+            assert_eq!(got, wanted);
+        }
+
+        #[test]
+        fn count_entities_and_relationships() {
+            // arrange
+            let mut erd = ERD::new().with_entity(Entity::new(ALBUM_ID));
+            assert_eq!(erd.entity_count(), 1);
+            assert_eq!(erd.relationship_count(), 0);
+            // act
+            erd.add_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            // assert
+            assert_eq!(erd.entity_count(), 2); // SONG is created by the relationship
+            assert_eq!(erd.relationship_count(), 1);
+        }
+
+        #[test]
+        fn entities_accessor_has_stable_order() {
+            // arrange
+            let erd = ERD::new()
+                .with_entity(Entity::new(SONG_ID))
+                .with_entity(Entity::new("ARTIST"))
+                .with_entity(Entity::new(ALBUM_ID).with_namespace("music"));
+            // act
+            let got = erd.entities();
+            // assert
+            assert_eq!(got.len(), erd.entity_count());
+            let ids: Vec<&str> = got.map(|entity| entity.id.as_str()).collect();
+            assert_eq!(ids, vec!["ARTIST", SONG_ID, ALBUM_ID]);
+            let again: Vec<&str> = erd.entities().map(|entity| entity.id.as_str()).collect();
+            assert_eq!(again, ids);
+        }
+
+        #[test]
+        fn empty_and_title_only_erds_are_empty() {
+            // arrange
+            let mut titled = ERD::new();
+            titled.title = Some("Music".to_string());
+            let populated = ERD::new().with_entity(Entity::new(ALBUM_ID));
+            // act & assert
+            assert!(ERD::new().is_empty());
+            assert!(titled.is_empty());
+            assert!(!populated.is_empty());
+        }
+
+        #[test]
+        fn find_isolated_entities() {
+            // arrange
+            let erd = ERD::new()
+                .with_entity(Entity::new("ARTIST"))
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ));
+            // act
+            let got: Vec<&str> = erd
+                .isolated_entities()
+                .iter()
+                .map(|entity| entity.id.as_str())
+                .collect();
+            // assert
+            assert_eq!(got, vec!["ARTIST"]);
+        }
+
+        #[test]
+        fn list_relationships_for_entity() {
+            // arrange
+            let erd = ERD::new()
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ))
+                .with_relationship(Relationship::new(
+                    "ARTIST",
+                    ALBUM_ID,
+                    Cardinality::OneOrMore,
+                    Cardinality::OneOrMore,
+                ));
+            // assert
+            assert_eq!(erd.relationships_for(&EntityId::from(ALBUM_ID)).len(), 2);
+            assert_eq!(erd.relationships_for(&EntityId::from(SONG_ID)).len(), 1);
+            assert!(erd.relationships_for(&EntityId::from("FAKE")).is_empty());
+        }
+
+        #[test]
+        fn clear_relationships_keeps_entities() {
+            // arrange
+            let mut erd = ERD::new().with_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            // act
+            erd.clear_relationships();
+            // assert
+            assert!(erd.relationships.is_empty());
+            assert_eq!(erd.entities.len(), 2);
+        }
+
+        #[test]
+        fn add_relationship_for_each_reference_to_the_same_entity() {
+            // arrange
+            let mut erd = ERD::new().with_entity(
+                Entity::new("TASK")
+                    .with_attribute(Attribute::new("int", "id").as_primary_key())
+                    .with_attribute(Attribute::new("int", "created_by").references("USER"))
+                    .with_attribute(Attribute::new("int", "updated_by").references("USER")),
+            );
+            let wanted = concat!(
+                "erDiagram\n",
+                "    %% Entities start\n",
+                "    TASK {\n",
+                "        int id PK\n",
+                "        int created_by FK\n",
+                "        int updated_by FK\n",
+                "    }\n",
+                "    USER\n",
+                "    %% Entities end\n",
+                "    %% Relationships start\n",
+                "    USER ||..o{ TASK : \"created_by\"\n",
+                "    USER ||..o{ TASK : \"updated_by\"\n",
+                "    %% Relationships end",
+            );
+            // act
+            erd.add_reference_relationships();
+            erd.add_reference_relationships();
+            // assert
+            let got = erd.to_string();
+            assert_eq!(erd.relationship_count(), 2);
+            crate::assert_mermaid_eq!(got, wanted);
+            assert!(erd.contains_entity(&EntityId::from("USER")));
+            let task = erd
+                .get_entity_by_id(&EntityId::from("TASK"))
+                .expect("Expected entity");
+            assert!(task.attributes[1].key.is_foreign);
+        }
+
+        #[test]
+        fn display_empty_diagram() {
+            // arrange
+            let wanted = "erDiagram";
+            let erd = ERD::new();
+            // act
+            let got = erd.to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
+
+        #[test]
+        fn display_erd_with_entities_and_their_attributes() {
+            // arrange
+            let attr_type = "string";
+            let erd = ERD::new()
+                .with_entity(
+                    Entity::new(ALBUM_ID)
+                        .with_attribute(Attribute::new(attr_type, "foo"))
+                        .with_attribute(Attribute::new(attr_type, "bar")),
+                )
+                .with_entity(Entity::new(SONG_ID));
+            let album_wanted = concat!(
+                "    ALBUM {\n",
+                "        string foo\n",
+                "        string bar\n",
+                "    }",
+            );
+            let song_wanted = "SONG";
+            // act
+            let got = erd.to_string();
+            // assert
+            assert!(got.contains(album_wanted));
             assert!(got.contains(song_wanted));
         }
 
+        #[test]
+        fn render_with_crlf_line_endings() {
+            // arrange
+            let erd = ERD::new().with_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            let options = RenderOptions::new().with_line_ending(LineEnding::Crlf);
+            // act
+            let got = erd.render_with(&options);
+            // assert
+            let wanted = concat!(
+                "erDiagram\r\n",
+                "    %% Entities start\r\n",
+                "    ALBUM\r\n",
+                "    SONG\r\n",
+                "    %% Entities end\r\n",
+                "    %% Relationships start\r\n",
+                "    ALBUM ||--|{ SONG : \"\"\r\n",
+                "    %% Relationships end",
+            );
+            assert_eq!(got, wanted);
+        }
+
+        #[test]
+        fn render_with_default_and_overridden_keyword() {
+            // arrange
+            let erd = ERD::new();
+            let options = RenderOptions::new().with_keyword("customDiagram");
+            // assert
+            assert_eq!(ERD::DIAGRAM_KEYWORD, "erDiagram");
+            assert_eq!(erd.render_with(&RenderOptions::new()), "erDiagram");
+            assert_eq!(erd.render_with(&options), "customDiagram");
+        }
+
+        #[test]
+        fn render_pretty_and_minified() {
+            // arrange
+            let erd = ERD::new().with_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            // act
+            let pretty = erd.to_pretty();
+            let minified = erd.to_minified();
+            // assert
+            assert_eq!(pretty, erd.to_string());
+            assert!(!minified.contains("%%"));
+            assert_eq!(minified.lines().count(), pretty.lines().count() - 4);
+        }
+
+        #[test]
+        fn render_relationships_grouped_by_left_entity() {
+            // arrange
+            let erd = ERD::new()
+                .with_relationship(Relationship::new(
+                    SONG_ID,
+                    "PLAY",
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                ))
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ))
+                .with_relationship(Relationship::new(
+                    SONG_ID,
+                    "GENRE",
+                    Cardinality::ZeroOrMore,
+                    Cardinality::ExactlyOne,
+                ));
+            let wanted = concat!(
+                "erDiagram\n",
+                "    %% Entities start\n",
+                "    ALBUM\n",
+                "    GENRE\n",
+                "    PLAY\n",
+                "    SONG\n",
+                "    %% Entities end\n",
+                "    %% Relationships start\n",
+                "    %% relationships from ALBUM\n",
+                "    ALBUM ||--|{ SONG : \"\"\n",
+                "    %% relationships from SONG\n",
+                "    SONG ||--o{ PLAY : \"\"\n",
+                "    SONG }o--|| GENRE : \"\"\n",
+                "    %% Relationships end",
+            );
+            let options = RenderOptions::new().with_group_relationships(true);
+            // act
+            let got = erd.render_with(&options);
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn render_relationships_with_aligned_glyphs() {
+            // arrange
+            let erd = ERD::new()
+                .with_relationship(Relationship::new(
+                    ALBUM_ID,
+                    SONG_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::OneOrMore,
+                ))
+                .with_relationship(Relationship::new(
+                    "RECORD_LABEL",
+                    ALBUM_ID,
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                ))
+                .with_relationship(
+                    Relationship::new(
+                        "ARTIST",
+                        ALBUM_ID,
+                        Cardinality::OneOrMore,
+                        Cardinality::OneOrMore,
+                    )
+                    .as_non_identifying(),
+                );
+            let wanted = concat!(
+                "erDiagram\n",
+                "    %% Entities start\n",
+                "    ALBUM\n",
+                "    ARTIST\n",
+                "    RECORD_LABEL\n",
+                "    SONG\n",
+                "    %% Entities end\n",
+                "    %% Relationships start\n",
+                "    ALBUM        ||--|{ SONG : \"\"\n",
+                "    RECORD_LABEL ||--o{ ALBUM : \"\"\n",
+                "    ARTIST       }|..|{ ALBUM : \"\"\n",
+                "    %% Relationships end",
+            );
+            let options = RenderOptions::new().with_align_relationships(true);
+            // act
+            let got = erd.render_with(&options);
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+            assert!(!erd.to_string().contains("ALBUM  "));
+        }
+
+        #[test]
+        fn render_accessibility_directives_after_keyword() {
+            // arrange
+            let erd = ERD::new()
+                .with_entity(Entity::new(ALBUM_ID))
+                .with_acc_title("Music catalog")
+                .with_acc_descr("Albums and their songs");
+            let wanted = concat!(
+                "erDiagram\n",
+                "    accTitle: Music catalog\n",
+                "    accDescr: Albums and their songs\n",
+                "    %% Entities start\n",
+                "    ALBUM\n",
+                "    %% Entities end",
+            );
+            // act
+            let got = erd.to_string();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+            let unset = ERD::new().with_entity(Entity::new(ALBUM_ID)).to_string();
+            assert!(!unset.contains("accTitle") && !unset.contains("accDescr"));
+        }
+
+        #[test]
+        fn render_multiline_acc_descr_as_block() {
+            // arrange
+            let erd = ERD::new().with_acc_descr("Albums and songs\nfor the catalog");
+            let wanted = concat!(
+                "erDiagram\n",
+                "    accDescr {\n",
+                "        Albums and songs\n",
+                "        for the catalog\n",
+                "    }",
+            );
+            // act
+            let got = erd.to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
+
+        #[test]
+        fn render_compact_without_section_comments() {
+            // arrange
+            let erd = ERD::new().with_relationship(Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ));
+            let wanted = concat!(
+                "erDiagram\n",
+                "    ALBUM\n",
+                "    SONG\n",
+                "    ALBUM ||--|{ SONG : \"\"",
+            );
+            // act
+            let got = erd.render_with(&RenderOptions::new().with_compact(true));
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn display_erd_with_entities_in_namespaces() {
+            // arrange
+            let erd = ERD::new()
+                .with_entity(Entity::new("ARTIST"))
+                .with_entity(Entity::new(SONG_ID).with_namespace("catalog"))
+                .with_entity(Entity::new(ALBUM_ID).with_namespace("catalog"))
+                .with_entity(Entity::new("PLAY").with_namespace("analytics"))
+                .with_relationship(Relationship::new(
+                    SONG_ID,
+                    "PLAY",
+                    Cardinality::ExactlyOne,
+                    Cardinality::ZeroOrMore,
+                ));
+            let wanted = concat!(
+                "erDiagram\n",
+                "    %% Entities start\n",
+                "    ARTIST\n",
+                "    %% Namespace analytics start\n",
+                "    PLAY\n",
+                "    %% Namespace analytics end\n",
+                "    %% Namespace catalog start\n",
+                "    ALBUM\n",
+                "    SONG\n",
+                "    %% Namespace catalog end\n",
+                "    %% Entities end\n",
+                "    %% Relationships start\n",
+                "    SONG ||--o{ PLAY : \"\"\n",
+                "    %% Relationships end",
+            );
+            // act
+            let got = erd.to_string();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn display_erd_with_entity_comment() {
+            // arrange
+            let erd = ERD::new()
+                .with_entity(Entity::new(ALBUM_ID).with_comment("Albums released by an artist"))
+                .with_entity(Entity::new(SONG_ID));
+            let wanted = concat!(
+                "erDiagram\n",
+                "    %% Entities start\n",
+                "    %% Albums released by an artist\n",
+                "    ALBUM\n",
+                "    SONG\n",
+                "    %% Entities end",
+            );
+            // act
+            let got = erd.to_string();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
         #[test]
         fn display_erd_with_relationships() {
             // arrange
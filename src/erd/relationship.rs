@@ -1,39 +1,128 @@
 use core::fmt;
 
-#[derive(PartialEq, Debug)]
+use crate::render::RenderOptions;
+use crate::utils::escape_quoted;
+use crate::MormaidError;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Cardinality {
     ZeroOrOne,
     ExactlyOne,
     ZeroOrMore,
     OneOrMore,
 }
-enum Direction {
+/// The end of a relationship that a cardinality applies to.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Direction {
     Left,
     Right,
 }
 
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dir_str = match self {
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+        write!(f, "{dir_str}")
+    }
+}
+
 impl Cardinality {
-    // Combines Cardinality and Direction to format the relationship join symbol
+    /// Every known variant, in declaration order.
+    #[must_use]
+    pub fn all_variants() -> &'static [Self] {
+        &[
+            Cardinality::ZeroOrOne,
+            Cardinality::ExactlyOne,
+            Cardinality::ZeroOrMore,
+            Cardinality::OneOrMore,
+        ]
+    }
+
+    /// Combines Cardinality and Direction to get the relationship join symbol.
+    ///
+    /// The symbols are mirrored for each end of the relationship, e.g.
+    /// [`Cardinality::ZeroOrMore`] is `}o` on the left and `o{` on the right.
     #[allow(clippy::match_same_arms)]
-    fn fmt_with_direction(&self, dir: Direction) -> String {
+    #[must_use]
+    pub fn symbol(&self, dir: Direction) -> &'static str {
         match (self, dir) {
             // formatting left_cardinality
-            (Cardinality::ZeroOrOne, Direction::Left) => "|o".to_string(),
-            (Cardinality::ExactlyOne, Direction::Left) => "||".to_string(),
-            (Cardinality::ZeroOrMore, Direction::Left) => "}o".to_string(),
-            (Cardinality::OneOrMore, Direction::Left) => "}|".to_string(),
+            (Cardinality::ZeroOrOne, Direction::Left) => "|o",
+            (Cardinality::ExactlyOne, Direction::Left) => "||",
+            (Cardinality::ZeroOrMore, Direction::Left) => "}o",
+            (Cardinality::OneOrMore, Direction::Left) => "}|",
             // formatting right_cardinality
-            (Cardinality::ZeroOrOne, Direction::Right) => "o|".to_string(),
-            (Cardinality::ExactlyOne, Direction::Right) => "||".to_string(),
-            (Cardinality::ZeroOrMore, Direction::Right) => "o{".to_string(),
-            (Cardinality::OneOrMore, Direction::Right) => "|{".to_string(),
+            (Cardinality::ZeroOrOne, Direction::Right) => "o|",
+            (Cardinality::ExactlyOne, Direction::Right) => "||",
+            (Cardinality::ZeroOrMore, Direction::Right) => "o{",
+            (Cardinality::OneOrMore, Direction::Right) => "|{",
+        }
+    }
+
+    /// The symbol for this cardinality on the left end of a relationship.
+    #[must_use]
+    pub fn left_symbol(&self) -> &'static str {
+        self.symbol(Direction::Left)
+    }
+
+    /// The symbol for this cardinality on the right end of a relationship.
+    #[must_use]
+    pub fn right_symbol(&self) -> &'static str {
+        self.symbol(Direction::Right)
+    }
+
+    /// A human-readable phrase for the cardinality, e.g. "zero or more".
+    #[must_use]
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Cardinality::ZeroOrOne => "zero or one",
+            Cardinality::ExactlyOne => "exactly one",
+            Cardinality::ZeroOrMore => "zero or more",
+            Cardinality::OneOrMore => "one or more",
         }
     }
 }
 
+/// Parse a cardinality from its name written in `snake_case`.
+///
+/// The accepted names are `zero_or_one`, `exactly_one`, `zero_or_more`, and
+/// `one_or_more`, which makes it possible to build relationships from config files.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::erd::Cardinality;
+///
+/// let cardinality = Cardinality::try_from("one_or_more");
+/// assert_eq!(cardinality, Ok(Cardinality::OneOrMore));
+/// ```
+impl TryFrom<&str> for Cardinality {
+    type Error = MormaidError;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        match name {
+            "zero_or_one" => Ok(Cardinality::ZeroOrOne),
+            "exactly_one" => Ok(Cardinality::ExactlyOne),
+            "zero_or_more" => Ok(Cardinality::ZeroOrMore),
+            "one_or_more" => Ok(Cardinality::OneOrMore),
+            _ => Err(MormaidError::UnknownVariant {
+                kind: "cardinality",
+                value: name.to_string(),
+            }),
+        }
+    }
+}
+
+/// Display the cardinality with its right-side symbol, e.g. `o{` for [`Cardinality::ZeroOrMore`].
+///
+/// Use [`Cardinality::left_symbol()`] to get the mirrored symbol for the left end.
 impl std::fmt::Display for Cardinality {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.fmt_with_direction(Direction::Right))
+        write!(f, "{}", self.symbol(Direction::Right))
     }
 }
 
@@ -54,6 +143,8 @@ impl std::fmt::Display for Cardinality {
 ///     .with_label("has");
 /// ```
 #[must_use]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Relationship {
     // The id
     pub left_id: super::EntityId,
@@ -62,6 +153,23 @@ pub struct Relationship {
     pub right_cardinality: Cardinality,
     pub is_identifying: bool,
     pub label: String,
+    /// The name of the relationship read from right to left, e.g. "belongs to".
+    ///
+    /// Mermaid only supports one label per relationship, so the role shares the
+    /// label's quotes, see [`Relationship::with_role()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub role: Option<String>,
+    /// The minimum and (optional) maximum number of right entities, e.g. `2..5`.
+    ///
+    /// Mermaid can't draw numeric bounds, so they're rendered in place of a
+    /// missing label, see [`Relationship::with_bounds()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bounds: Option<(u32, Option<u32>)>,
+    /// The maximum number of characters per displayed line of the label.
+    ///
+    /// Only affects rendering, see [`Relationship::with_wrapped_label()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label_width: Option<usize>,
 }
 
 impl Relationship {
@@ -85,6 +193,9 @@ impl Relationship {
             right_cardinality,
             is_identifying: true,
             label: String::new(),
+            role: None,
+            bounds: None,
+            label_width: None,
         }
     }
 
@@ -103,30 +214,172 @@ impl Relationship {
         self.label = label.to_string();
         self
     }
+
+    /// Chain with `Relationship::new()` to add a label that's displayed on
+    /// multiple lines of at most `width` characters.
+    ///
+    /// The stored label is unchanged. When rendering, mermaid's `<br/>` line break
+    /// is inserted between words wherever the next word would exceed the width,
+    /// and a single word longer than the width is kept whole on its own line.
+    pub fn with_wrapped_label(mut self, label: &str, width: usize) -> Self {
+        self.label = label.to_string();
+        self.label_width = Some(width);
+        self
+    }
+
+    /// Name the relationship from the right entity's side.
+    ///
+    /// The label names the relationship from left to right and the role names it
+    /// from right to left. Mermaid only supports one label, so when both are set
+    /// they're rendered together as `"label / role"`, and a role without a label
+    /// is rendered on its own. Relationships without a role are unchanged.
+    pub fn with_role(mut self, role: &str) -> Self {
+        self.role = Some(role.to_string());
+        self
+    }
+
+    /// Add numeric bounds to the relationship, with no maximum if `max` is `None`.
+    ///
+    /// If the relationship doesn't have a label, the bounds are rendered as its
+    /// label, e.g. `"(2..5)"` or `"(1..*)"`. An explicit label always takes
+    /// precedence, and a role is still appended after the bounds.
+    ///
+    /// The bounds aren't checked, use [`Relationship::try_with_bounds()`] to
+    /// reject a `min` that's greater than `max`.
+    pub fn with_bounds(mut self, min: u32, max: Option<u32>) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Add numeric bounds to the relationship, see [`Relationship::with_bounds()`].
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::InvalidRange`] if `min` is greater than `max`.
+    pub fn try_with_bounds(self, min: u32, max: Option<u32>) -> Result<Self, MormaidError> {
+        match max {
+            Some(max) if min > max => Err(MormaidError::InvalidRange(format!("({min}..{max})"))),
+            _ => Ok(self.with_bounds(min, max)),
+        }
+    }
+
+    // Combine the label (or bounds) and the role (if populated) into the rendered label
+    fn rendered_label(&self) -> String {
+        let label = self.label().map(str::to_string).or_else(|| {
+            self.bounds.map(|(min, max)| match max {
+                Some(max) => format!("({min}..{max})"),
+                None => format!("({min}..*)"),
+            })
+        });
+        match (label, self.role.as_deref()) {
+            (Some(label), Some(role)) => format!("{label} / {role}"),
+            (None, Some(role)) => role.to_string(),
+            (label, None) => label.unwrap_or_default(),
+        }
+    }
+
+    /// Describe the relationship in a sentence read from left to right,
+    /// e.g. "Each ALBUM includes one or more SONG".
+    ///
+    /// The label is used as the verb, or "has" if the relationship doesn't have one.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        format!(
+            "Each {} {} {} {}",
+            self.left_id.as_str(),
+            self.label().unwrap_or("has"),
+            self.right_cardinality.describe(),
+            self.right_id.as_str(),
+        )
+    }
+
+    /// The label of the relationship, or `None` if it doesn't have one.
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        if self.label.is_empty() {
+            None
+        } else {
+            Some(&self.label)
+        }
+    }
+
+    /// Replace the label of an existing relationship.
+    pub fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+    }
+
+    /// Remove the label from an existing relationship.
+    pub fn clear_label(&mut self) {
+        self.label.clear();
+    }
+
+    /// Check if two relationships connect the same pair of entities.
+    ///
+    /// Direction is ignored, so `A--B` and `B--A` are considered the same pair.
+    /// Cardinality, label, and identification are also ignored.
+    #[must_use]
+    pub fn same_pair(&self, other: &Relationship) -> bool {
+        (self.left_id == other.left_id && self.right_id == other.right_id)
+            || (self.left_id == other.right_id && self.right_id == other.left_id)
+    }
 }
 
-impl fmt::Display for Relationship {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Relationship {
+    // Render the relationship, escaping its label with the style in the options
+    pub(crate) fn render(&self, options: &RenderOptions) -> String {
+        self.render_padded(options, 0)
+    }
+
+    // Render the relationship with the left id padded to at least `width` characters
+    pub(crate) fn render_padded(&self, options: &RenderOptions, width: usize) -> String {
         // format the left and right ends based on their cardinality
         let left_str = format!(
-            "{} {}",
+            "{:width$} {}",
             self.left_id.as_str(),
-            self.left_cardinality.fmt_with_direction(Direction::Left),
+            self.left_cardinality.symbol(Direction::Left),
         );
-        // also add the label to the right end
+        // also add the label to the right end, wrapped before escaping if it has a
+        // width so that the escapes don't count towards the width
+        let label = self.rendered_label();
+        let label = match self.label_width {
+            Some(width) => wrap_label(&label, width)
+                .iter()
+                .map(|line| escape_quoted(line, options.escape_style))
+                .collect::<Vec<String>>()
+                .join("<br/>"),
+            None => escape_quoted(&label, options.escape_style),
+        };
         let right_str = format!(
-            "{} {} : \"{}\"",
-            self.right_cardinality.fmt_with_direction(Direction::Right),
+            "{} {} : \"{label}\"",
+            self.right_cardinality.symbol(Direction::Right),
             self.right_id.as_str(),
-            self.label
         );
         // format the relationship as a solid or dashed line
-        let relationship_str = if self.is_identifying {
+        if self.is_identifying {
             format!("{left_str}--{right_str}") // solid line
         } else {
             format!("{left_str}..{right_str}") // dashed line
-        };
-        write!(f, "{relationship_str}")
+        }
+    }
+}
+
+// Split the words of a label into lines, starting a new line wherever the line would exceed the width
+fn wrap_label(label: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in label.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+impl fmt::Display for Relationship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(&RenderOptions::default()))
     }
 }
 
@@ -138,6 +391,8 @@ mod tests {
 
     use super::super::*;
     use super::*;
+    use crate::render::EscapeStyle;
+    use std::collections::HashMap;
 
     const ALBUM_ID: &str = "ALBUM";
     const SONG_ID: &str = "SONG";
@@ -158,6 +413,282 @@ mod tests {
         assert_eq!(relationship.right_cardinality, Cardinality::OneOrMore);
     }
 
+    #[test]
+    fn test_cardinality_symbols_for_each_direction() {
+        // arrange
+        let wanted = [
+            (Cardinality::ZeroOrOne, Direction::Left, "|o"),
+            (Cardinality::ExactlyOne, Direction::Left, "||"),
+            (Cardinality::ZeroOrMore, Direction::Left, "}o"),
+            (Cardinality::OneOrMore, Direction::Left, "}|"),
+            (Cardinality::ZeroOrOne, Direction::Right, "o|"),
+            (Cardinality::ExactlyOne, Direction::Right, "||"),
+            (Cardinality::ZeroOrMore, Direction::Right, "o{"),
+            (Cardinality::OneOrMore, Direction::Right, "|{"),
+        ];
+        for (cardinality, dir, symbol) in wanted {
+            // act
+            let got = cardinality.symbol(dir);
+            // assert
+            assert_eq!(got, symbol, "wrong {dir} symbol for {cardinality:?}");
+        }
+    }
+
+    #[test]
+    fn test_left_and_right_symbols_for_each_variant() {
+        // arrange
+        let symbols = [
+            (Cardinality::ZeroOrOne, "|o", "o|"),
+            (Cardinality::ExactlyOne, "||", "||"),
+            (Cardinality::ZeroOrMore, "}o", "o{"),
+            (Cardinality::OneOrMore, "}|", "|{"),
+        ];
+        for (cardinality, left, right) in symbols {
+            // act & assert
+            assert_eq!(cardinality.left_symbol(), left);
+            assert_eq!(cardinality.right_symbol(), right);
+            assert_eq!(cardinality.to_string(), right);
+        }
+    }
+
+    #[test]
+    fn test_display_direction() {
+        // assert
+        assert_eq!(Direction::Left.to_string(), "left");
+        assert_eq!(Direction::Right.to_string(), "right");
+    }
+
+    #[test]
+    fn test_count_relationships_by_cardinality() {
+        // arrange
+        let relationships = [
+            Relationship::new(
+                ALBUM_ID,
+                SONG_ID,
+                Cardinality::ExactlyOne,
+                Cardinality::OneOrMore,
+            ),
+            Relationship::new(
+                "ARTIST",
+                SONG_ID,
+                Cardinality::ZeroOrOne,
+                Cardinality::OneOrMore,
+            ),
+            Relationship::new(
+                SONG_ID,
+                "PLAY",
+                Cardinality::ExactlyOne,
+                Cardinality::ZeroOrMore,
+            ),
+        ];
+        // act
+        let mut counts: HashMap<&Cardinality, usize> = HashMap::new();
+        for r in &relationships {
+            *counts.entry(&r.right_cardinality).or_default() += 1;
+        }
+        // assert
+        assert_eq!(counts.get(&Cardinality::OneOrMore), Some(&2));
+        assert_eq!(counts.get(&Cardinality::ZeroOrMore), Some(&1));
+        assert_eq!(counts.get(&Cardinality::ExactlyOne), None);
+    }
+
+    #[test]
+    fn test_cardinality_from_valid_names() {
+        // assert
+        assert_eq!(
+            Cardinality::try_from("zero_or_one"),
+            Ok(Cardinality::ZeroOrOne)
+        );
+        assert_eq!(
+            Cardinality::try_from("exactly_one"),
+            Ok(Cardinality::ExactlyOne)
+        );
+        assert_eq!(
+            Cardinality::try_from("zero_or_more"),
+            Ok(Cardinality::ZeroOrMore)
+        );
+        assert_eq!(
+            Cardinality::try_from("one_or_more"),
+            Ok(Cardinality::OneOrMore)
+        );
+    }
+
+    #[test]
+    fn test_cardinality_from_invalid_name() {
+        // act
+        let got = Cardinality::try_from("many");
+        // assert
+        assert_eq!(
+            got,
+            Err(MormaidError::UnknownVariant {
+                kind: "cardinality",
+                value: "many".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_same_pair_when_direction_is_swapped() {
+        // arrange
+        let album_song = Relationship::new(
+            ALBUM_ID,
+            SONG_ID,
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        );
+        let song_album = Relationship::new(
+            SONG_ID,
+            ALBUM_ID,
+            Cardinality::OneOrMore,
+            Cardinality::ExactlyOne,
+        );
+        // assert
+        assert!(album_song.same_pair(&song_album));
+        assert!(song_album.same_pair(&album_song));
+    }
+
+    #[test]
+    fn test_same_pair_for_different_entities() {
+        // arrange
+        let album_song = Relationship::new(
+            ALBUM_ID,
+            SONG_ID,
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        );
+        let album_artist = Relationship::new(
+            ALBUM_ID,
+            "ARTIST",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        );
+        // assert
+        assert!(!album_song.same_pair(&album_artist));
+    }
+
+    #[test]
+    fn test_render_quoted_label_with_each_escape_style() {
+        // arrange
+        let rel = Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        )
+        .with_label(r#"has "hits""#);
+        let styles = [
+            (EscapeStyle::Entity, r#""has #quot;hits#quot;""#),
+            (EscapeStyle::Html, r#""has &quot;hits&quot;""#),
+            (EscapeStyle::Backslash, r#""has \"hits\"""#),
+        ];
+        for (style, label) in styles {
+            let options = RenderOptions::new().with_escape_style(style);
+            // act
+            let got = rel.render(&options);
+            // assert
+            assert_eq!(got, format!("ALBUM ||--|{{ SONG : {label}"));
+        }
+    }
+
+    #[test]
+    fn test_display_with_label_and_role() {
+        // arrange
+        let rel = Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        );
+        // act
+        let both = rel.clone().with_label("includes").with_role("appears on");
+        let role_only = rel.clone().with_role("appears on");
+        // assert
+        assert_eq!(
+            both.to_string(),
+            "ALBUM ||--|{ SONG : \"includes / appears on\""
+        );
+        assert_eq!(role_only.to_string(), "ALBUM ||--|{ SONG : \"appears on\"");
+        assert_eq!(rel.to_string(), "ALBUM ||--|{ SONG : \"\"");
+    }
+
+    #[test]
+    fn test_display_bounds_in_place_of_label() {
+        // arrange
+        let rel = Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        );
+        // act
+        let bounded = rel.clone().with_bounds(2, Some(5));
+        let unbounded = rel.clone().with_bounds(1, None);
+        // assert
+        assert_eq!(bounded.to_string(), "ALBUM ||--|{ SONG : \"(2..5)\"");
+        assert_eq!(unbounded.to_string(), "ALBUM ||--|{ SONG : \"(1..*)\"");
+    }
+
+    #[test]
+    fn test_try_with_bounds_rejects_min_greater_than_max() {
+        // arrange
+        let rel = Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        );
+        // act
+        let valid = rel.clone().try_with_bounds(2, Some(2)).map(|r| r.bounds);
+        let unbounded = rel.clone().try_with_bounds(5, None).map(|r| r.bounds);
+        let invalid = rel.try_with_bounds(5, Some(2)).map(|r| r.bounds);
+        // assert
+        assert_eq!(valid, Ok(Some((2, Some(2)))));
+        assert_eq!(unbounded, Ok(Some((5, None))));
+        assert_eq!(
+            invalid,
+            Err(MormaidError::InvalidRange("(5..2)".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_display_explicit_label_takes_precedence_over_bounds() {
+        // arrange
+        let rel = Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        )
+        .with_bounds(2, Some(5))
+        .with_label("includes");
+        // act
+        let got = rel.to_string();
+        // assert
+        assert_eq!(got, "ALBUM ||--|{ SONG : \"includes\"");
+        assert_eq!(rel.bounds, Some((2, Some(5))));
+    }
+
+    #[test]
+    fn test_set_read_and_clear_label() {
+        // arrange
+        let mut rel = Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        );
+        assert_eq!(rel.label(), None);
+        // act
+        rel.set_label("includes");
+        // assert
+        assert_eq!(rel.label(), Some("includes"));
+        // act
+        rel.clear_label();
+        // assert
+        assert_eq!(rel.label(), None);
+        assert_eq!(rel.to_string(), "ALBUM ||--|{ SONG : \"\"");
+    }
+
     #[test]
     fn test_display_identifying_without_a_label() {
         // arrange
@@ -192,4 +723,107 @@ mod tests {
         // assert
         assert_eq!(got, wanted);
     }
+
+    #[test]
+    fn test_display_wrapped_label_breaks_at_width() {
+        // arrange
+        let label = "includes every song recorded for the album";
+        let relationship = Relationship::new(
+            ALBUM_ID,
+            SONG_ID,
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        )
+        .with_wrapped_label(label, 15);
+        let wanted = "ALBUM ||--|{ SONG : \"includes every<br/>song recorded<br/>for the album\"";
+        // act
+        let got = relationship.to_string();
+        // assert
+        assert_eq!(got, wanted);
+        assert_eq!(relationship.label(), Some(label));
+    }
+
+    #[test]
+    fn test_wrapped_label_keeps_long_words_whole() {
+        // act
+        let got = wrap_label("a supercalifragilistic label", 5);
+        // assert
+        assert_eq!(got, ["a", "supercalifragilistic", "label"]);
+    }
+
+    #[test]
+    fn test_display_wrapped_label_counts_quotes_before_escaping() {
+        // arrange
+        let relationship = Relationship::new(
+            ALBUM_ID,
+            SONG_ID,
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        )
+        .with_wrapped_label("the \"best\" song ever", 10);
+        let wanted = "ALBUM ||--|{ SONG : \"the #quot;best#quot;<br/>song ever\"";
+        // act
+        let got = relationship.to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn test_describe_each_cardinality() {
+        // arrange
+        let phrases = [
+            (Cardinality::ZeroOrOne, "zero or one"),
+            (Cardinality::ExactlyOne, "exactly one"),
+            (Cardinality::ZeroOrMore, "zero or more"),
+            (Cardinality::OneOrMore, "one or more"),
+        ];
+        for (cardinality, wanted) in phrases {
+            // act
+            let got = cardinality.describe();
+            // assert
+            assert_eq!(got, wanted);
+        }
+    }
+
+    #[test]
+    fn test_describe_relationship_as_a_sentence() {
+        // arrange
+        let labeled = Relationship::new(
+            ALBUM_ID,
+            SONG_ID,
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        )
+        .with_label("includes");
+        let unlabeled = Relationship::new(
+            SONG_ID,
+            "GENRE",
+            Cardinality::ZeroOrMore,
+            Cardinality::ZeroOrOne,
+        );
+        // act
+        let got = (labeled.describe(), unlabeled.describe());
+        // assert
+        assert_eq!(got.0, "Each ALBUM includes one or more SONG");
+        assert_eq!(got.1, "Each SONG has zero or one GENRE");
+    }
+
+    // Index each cardinality, failing to compile when one is missing from the match
+    fn position(cardinality: &Cardinality) -> usize {
+        match cardinality {
+            Cardinality::ZeroOrOne => 0,
+            Cardinality::ExactlyOne => 1,
+            Cardinality::ZeroOrMore => 2,
+            Cardinality::OneOrMore => 3,
+        }
+    }
+
+    #[test]
+    fn test_all_variants_lists_every_cardinality() {
+        // act
+        let got: Vec<usize> = Cardinality::all_variants().iter().map(position).collect();
+        // assert
+        assert_eq!(Cardinality::all_variants().len(), 4);
+        assert_eq!(got, [0, 1, 2, 3]);
+    }
 }
@@ -0,0 +1,207 @@
+use std::fmt;
+
+/// Errors returned by the fallible methods used to build diagrams.
+#[derive(Debug, PartialEq)]
+pub enum MormaidError {
+    /// A relationship references an entity that hasn't been added to the diagram.
+    MissingEntity(String),
+    /// A relationship references an element that hasn't been added to the diagram.
+    MissingElement(String),
+    /// A point on a quadrant chart has a coordinate outside of the 0 to 1 range.
+    InvalidCoordinate(String),
+    /// A git graph operation references a branch that was never created.
+    MissingBranch(String),
+    /// A git graph operation creates a branch that already exists.
+    DuplicateBranch(String),
+    /// A packet field or a relationship's bounds end before they start.
+    InvalidRange(String),
+    /// A packet field overlaps with the bits of the previous field.
+    OverlappingRange(String),
+    /// A packet field leaves a gap after the bits of the previous field.
+    NonContiguousRange(String),
+    /// A series on an XY chart doesn't have one value per x-axis category.
+    SeriesLengthMismatch { expected: usize, found: usize },
+    /// A string doesn't match any of the variants of the named enum.
+    UnknownVariant { kind: &'static str, value: String },
+    /// An entity is added or renamed with an id that is already used in the diagram.
+    DuplicateEntity(String),
+    /// A kanban column is added with a title that is already used on the board.
+    DuplicateColumn(String),
+    /// A requirement id is empty or contains characters other than letters, digits, `.`, `-`, or `_`.
+    InvalidId(String),
+    /// A requirement name is empty or isn't a single token of letters, digits, `-`, or `_`.
+    InvalidName(String),
+    /// An entity id contains characters other than letters, digits, `-`, or `_`.
+    InvalidEntityId(String),
+    /// An entity has more than one attribute with the same name.
+    DuplicateAttribute { entity: String, attribute: String },
+    /// An entity isn't the left or right entity of any relationship.
+    IsolatedEntity(String),
+    /// A relationship references an entity whose id only differs from another entity's id by case.
+    CaseMismatch { found: String, expected: String },
+    /// An entity has a foreign key attribute but no relationships.
+    UnrelatedForeignKey { entity: String, attribute: String },
+    /// A requirement diagram relationship has the same source and target.
+    SelfRelationship(String),
+    /// An element docref isn't a valid URI or relative reference.
+    InvalidDocref(String),
+    /// A composite unique key names an attribute that the entity doesn't have.
+    MissingAttribute { entity: String, attribute: String },
+    /// A name is used by an element in one diagram and a requirement in the other.
+    AmbiguousName(String),
+}
+
+impl fmt::Display for MormaidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MormaidError::MissingEntity(id) => {
+                write!(f, "{id} isn't found in the list of entities")
+            }
+            MormaidError::MissingElement(name) => {
+                write!(f, "{name} isn't found in the list of elements")
+            }
+            MormaidError::InvalidCoordinate(name) => {
+                write!(f, "{name} has a coordinate outside of the 0 to 1 range")
+            }
+            MormaidError::MissingBranch(name) => {
+                write!(f, "{name} isn't found in the list of branches")
+            }
+            MormaidError::DuplicateBranch(name) => {
+                write!(f, "{name} is already in the list of branches")
+            }
+            MormaidError::InvalidRange(label) => {
+                write!(f, "{label} ends before it starts")
+            }
+            MormaidError::OverlappingRange(label) => {
+                write!(f, "{label} overlaps with the previous field")
+            }
+            MormaidError::NonContiguousRange(label) => {
+                write!(f, "{label} doesn't start right after the previous field")
+            }
+            MormaidError::SeriesLengthMismatch { expected, found } => {
+                write!(
+                    f,
+                    "expected {expected} values in the series but found {found}"
+                )
+            }
+            MormaidError::UnknownVariant { kind, value } => {
+                write!(f, "{value} isn't a valid {kind}")
+            }
+            MormaidError::DuplicateEntity(id) => {
+                write!(f, "{id} is already in the list of entities")
+            }
+            MormaidError::DuplicateColumn(title) => {
+                write!(f, "{title} is already in the list of columns")
+            }
+            MormaidError::InvalidId(id) => {
+                write!(f, "{id:?} isn't a valid requirement id")
+            }
+            MormaidError::InvalidName(name) => {
+                write!(f, "{name:?} isn't a valid requirement name")
+            }
+            MormaidError::InvalidEntityId(id) => {
+                write!(f, "{id:?} isn't a valid entity id")
+            }
+            MormaidError::DuplicateAttribute { entity, attribute } => {
+                write!(f, "{attribute} is defined more than once in {entity}")
+            }
+            MormaidError::IsolatedEntity(id) => {
+                write!(f, "{id} isn't in any relationships")
+            }
+            MormaidError::CaseMismatch { found, expected } => {
+                write!(f, "{found} only differs from {expected} by case")
+            }
+            MormaidError::UnrelatedForeignKey { entity, attribute } => {
+                write!(
+                    f,
+                    "{attribute} is a foreign key but {entity} isn't in any relationships"
+                )
+            }
+            MormaidError::SelfRelationship(name) => {
+                write!(f, "{name} can't have a relationship with itself")
+            }
+            MormaidError::InvalidDocref(docref) => {
+                write!(f, "{docref:?} isn't a valid docref")
+            }
+            MormaidError::MissingAttribute { entity, attribute } => {
+                write!(
+                    f,
+                    "{attribute} isn't found in the list of attributes for {entity}"
+                )
+            }
+            MormaidError::AmbiguousName(name) => {
+                write!(f, "{name} is both an element and a requirement")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MormaidError {}
+
+/// Errors returned when a diagram is rendered to an image by an external tool.
+#[derive(Debug)]
+pub enum RenderError {
+    /// The mermaid CLI (`mmdc`) isn't installed or isn't on the `PATH`.
+    MissingCli,
+    /// Reading or writing the files passed to the mermaid CLI failed.
+    Io(std::io::Error),
+    /// The mermaid CLI ran but exited unsuccessfully, with its stderr output.
+    Failed(String),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::MissingCli => {
+                write!(
+                    f,
+                    "mmdc isn't found on the PATH, install @mermaid-js/mermaid-cli"
+                )
+            }
+            RenderError::Io(err) => write!(f, "couldn't pass the diagram to mmdc: {err}"),
+            RenderError::Failed(stderr) => write!(f, "mmdc failed to render the diagram: {stderr}"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RenderError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RenderError {
+    fn from(err: std::io::Error) -> Self {
+        RenderError::Io(err)
+    }
+}
+
+/// An error returned when mermaid text can't be parsed back into a diagram.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    /// The line of the input that couldn't be parsed, starting from 1, or 0
+    /// if the error applies to the input as a whole.
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    #[must_use]
+    pub fn new(line: usize, message: &str) -> Self {
+        ParseError {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
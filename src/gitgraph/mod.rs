@@ -0,0 +1,200 @@
+use std::fmt;
+
+pub mod operation;
+
+use crate::MormaidError;
+pub use operation::{Commit, Operation};
+
+/// The branch that mermaid creates and checks out at the start of every git graph.
+pub const DEFAULT_BRANCH: &str = "main";
+
+// ================================================================
+// GitGraph struct and implementation
+// ================================================================
+
+/// Represents a mermaid `gitGraph` diagram.
+///
+/// Operations are stored and rendered in the order they're added, since the
+/// order of the operations determines the history shown in the diagram.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::gitgraph::{Commit, GitGraph};
+///
+/// let graph = GitGraph::new()
+///     .with_commit(Commit::new())
+///     .with_branch("develop")
+///     .and_then(|graph| graph.with_checkout("develop"))
+///     .map(|graph| graph.with_commit(Commit::new().with_tag("v0.1")))
+///     .and_then(|graph| graph.with_checkout("main"))
+///     .and_then(|graph| graph.with_merge("develop"))
+///     .unwrap();
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct GitGraph {
+    pub operations: Vec<Operation>,
+}
+
+impl GitGraph {
+    pub fn new() -> Self {
+        GitGraph {
+            operations: Vec::new(),
+        }
+    }
+}
+
+// implement the Display trait
+impl fmt::Display for GitGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // initialize the gitGraph
+        let mut out_str = "gitGraph".to_string();
+        // append each operation on its own indented line
+        for op in &self.operations {
+            out_str += &format!("\n    {op}");
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+// =============================================
+// Implement GitGraph methods to manage operations
+// =============================================
+impl GitGraph {
+    /// Add an operation to the end of `GitGraph.operations`.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::DuplicateBranch`] when creating a branch that
+    /// already exists, or [`MormaidError::MissingBranch`] when checking out or
+    /// merging a branch that was never created. In both cases the operation isn't added.
+    pub fn add_operation(&mut self, op: Operation) -> Result<(), MormaidError> {
+        match &op {
+            Operation::Branch(name) if self.has_branch(name) => {
+                return Err(MormaidError::DuplicateBranch(name.clone()));
+            }
+            Operation::Checkout(name) | Operation::Merge(name) if !self.has_branch(name) => {
+                return Err(MormaidError::MissingBranch(name.clone()));
+            }
+            _ => (),
+        }
+        self.operations.push(op);
+        Ok(())
+    }
+
+    /// Add a commit to the currently checked out branch.
+    pub fn with_commit(mut self, commit: Commit) -> Self {
+        self.operations.push(Operation::Commit(commit));
+        self
+    }
+
+    /// Create a new branch from the currently checked out branch.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::DuplicateBranch`] if the branch already exists.
+    pub fn with_branch(mut self, name: &str) -> Result<Self, MormaidError> {
+        self.add_operation(Operation::Branch(name.to_string()))?;
+        Ok(self)
+    }
+
+    /// Check out an existing branch.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingBranch`] if the branch was never created.
+    pub fn with_checkout(mut self, name: &str) -> Result<Self, MormaidError> {
+        self.add_operation(Operation::Checkout(name.to_string()))?;
+        Ok(self)
+    }
+
+    /// Merge an existing branch into the currently checked out branch.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingBranch`] if the branch was never created.
+    pub fn with_merge(mut self, name: &str) -> Result<Self, MormaidError> {
+        self.add_operation(Operation::Merge(name.to_string()))?;
+        Ok(self)
+    }
+
+    // Check if a branch with the name provided is the default or was created
+    fn has_branch(&self, name: &str) -> bool {
+        name == DEFAULT_BRANCH
+            || self
+                .operations
+                .iter()
+                .any(|op| matches!(op, Operation::Branch(branch) if branch == name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const BRANCH: &str = "develop";
+
+    #[test]
+    fn display_empty_graph() {
+        // act
+        let got = GitGraph::new().to_string();
+        // assert
+        assert_eq!(got, "gitGraph");
+    }
+
+    #[test]
+    fn display_branch_commit_merge_sequence() {
+        // arrange
+        let wanted = concat!(
+            "gitGraph\n",
+            "    commit\n",
+            "    branch develop\n",
+            "    checkout develop\n",
+            "    commit id: \"Feature\"\n",
+            "    checkout main\n",
+            "    merge develop\n",
+            "    commit tag: \"v1.0\"",
+        );
+        // act
+        let got = GitGraph::new()
+            .with_commit(Commit::new())
+            .with_branch(BRANCH)
+            .and_then(|graph| graph.with_checkout(BRANCH))
+            .map(|graph| graph.with_commit(Commit::new().with_id("Feature")))
+            .and_then(|graph| graph.with_checkout(DEFAULT_BRANCH))
+            .and_then(|graph| graph.with_merge(BRANCH))
+            .map(|graph| graph.with_commit(Commit::new().with_tag("v1.0")))
+            .expect("Expected a valid sequence of operations")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn checkout_missing_branch_should_error() {
+        // arrange
+        let mut graph = GitGraph::new();
+        // act
+        let got = graph.add_operation(Operation::Checkout(BRANCH.to_string()));
+        // assert
+        assert_eq!(got, Err(MormaidError::MissingBranch(BRANCH.to_string())));
+        assert!(graph.operations.is_empty());
+    }
+
+    #[test]
+    fn merge_missing_branch_should_error() {
+        // act
+        let got = GitGraph::new().with_merge(BRANCH).err();
+        // assert
+        assert_eq!(got, Some(MormaidError::MissingBranch(BRANCH.to_string())));
+    }
+
+    #[test]
+    fn create_existing_branch_should_error() {
+        // act
+        let got = GitGraph::new()
+            .with_branch(BRANCH)
+            .and_then(|graph| graph.with_branch(BRANCH))
+            .err();
+        // assert
+        assert_eq!(got, Some(MormaidError::DuplicateBranch(BRANCH.to_string())));
+    }
+}
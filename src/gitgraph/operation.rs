@@ -0,0 +1,105 @@
+use std::fmt;
+
+/// Represents a commit on the currently checked out branch.
+#[must_use]
+#[derive(Debug, PartialEq, Default)]
+pub struct Commit {
+    pub id: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl Commit {
+    pub fn new() -> Self {
+        Commit {
+            id: None,
+            tag: None,
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Commit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out_str = "commit".to_string();
+        // format the commit id if it's populated
+        if let Some(id) = self.id.as_deref() {
+            out_str += &format!(" id: \"{id}\"");
+        }
+        // format the tag if it's populated
+        if let Some(tag) = self.tag.as_deref() {
+            out_str += &format!(" tag: \"{tag}\"");
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+/// Represents a single step in the history of a git graph.
+#[derive(Debug, PartialEq)]
+pub enum Operation {
+    Commit(Commit),
+    Branch(String),
+    Checkout(String),
+    Merge(String),
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Commit(commit) => write!(f, "{commit}"),
+            Operation::Branch(name) => write!(f, "branch {name}"),
+            Operation::Checkout(name) => write!(f, "checkout {name}"),
+            Operation::Merge(name) => write!(f, "merge {name}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BRANCH: &str = "develop";
+
+    #[test]
+    fn display_commit_without_id_or_tag() {
+        // act
+        let got = Commit::new().to_string();
+        // assert
+        assert_eq!(got, "commit");
+    }
+
+    #[test]
+    fn display_commit_with_id_and_tag() {
+        // arrange
+        let wanted = "commit id: \"Alpha\" tag: \"v1.0\"";
+        // act
+        let got = Commit::new().with_id("Alpha").with_tag("v1.0").to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_branch_operations() {
+        // assert
+        assert_eq!(
+            Operation::Branch(BRANCH.to_string()).to_string(),
+            "branch develop"
+        );
+        assert_eq!(
+            Operation::Checkout(BRANCH.to_string()).to_string(),
+            "checkout develop"
+        );
+        assert_eq!(
+            Operation::Merge(BRANCH.to_string()).to_string(),
+            "merge develop"
+        );
+    }
+}
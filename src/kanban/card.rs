@@ -0,0 +1,105 @@
+use std::fmt;
+
+// ==================================================================
+// Priority enum
+// ==================================================================
+
+/// The priorities that mermaid supports for a kanban card.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Priority {
+    VeryHigh,
+    High,
+    Low,
+    VeryLow,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let priority_str = match self {
+            Priority::VeryHigh => "Very High",
+            Priority::High => "High",
+            Priority::Low => "Low",
+            Priority::VeryLow => "Very Low",
+        };
+        write!(f, "{priority_str}")
+    }
+}
+
+// ==================================================================
+// Card struct and implementation
+// ==================================================================
+
+/// Represents a single card in a kanban column.
+#[must_use]
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub id: String,
+    pub title: String,
+    pub assigned: Option<String>,
+    pub priority: Option<Priority>,
+}
+
+impl Card {
+    pub fn new(id: &str, title: &str) -> Self {
+        Card {
+            id: id.to_string(),
+            title: title.to_string(),
+            assigned: None,
+            priority: None,
+        }
+    }
+
+    pub fn with_assigned(mut self, assigned: &str) -> Self {
+        self.assigned = Some(assigned.to_string());
+        self
+    }
+
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out_str = format!("{}[{}]", self.id, self.title);
+        // format the metadata (if populated) as a single @{ ... } block
+        let mut metadata = Vec::new();
+        if let Some(assigned) = self.assigned.as_deref() {
+            metadata.push(format!("assigned: '{assigned}'"));
+        }
+        if let Some(priority) = self.priority {
+            metadata.push(format!("priority: '{priority}'"));
+        }
+        if !metadata.is_empty() {
+            out_str += &format!("@{{ {} }}", metadata.join(", "));
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_card_without_metadata() {
+        // act
+        let got = Card::new("docs", "Write docs").to_string();
+        // assert
+        assert_eq!(got, "docs[Write docs]");
+    }
+
+    #[test]
+    fn display_card_with_metadata() {
+        // arrange
+        let wanted = "docs[Write docs]@{ assigned: 'widal001', priority: 'Very High' }";
+        // act
+        let got = Card::new("docs", "Write docs")
+            .with_assigned("widal001")
+            .with_priority(Priority::VeryHigh)
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use super::Card;
+use crate::utils::Indent;
+
+/// Represents a column (or lane) of a kanban board with its cards in order.
+#[must_use]
+#[derive(Debug, PartialEq)]
+pub struct Column {
+    pub id: String,
+    pub title: String,
+    pub cards: Vec<Card>,
+}
+
+impl Column {
+    pub fn new(id: &str, title: &str) -> Self {
+        Column {
+            id: id.to_string(),
+            title: title.to_string(),
+            cards: Vec::new(),
+        }
+    }
+
+    /// Add a card to the bottom of the column.
+    pub fn add_card(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    /// Add a card to the column on creation by chaining with [`Column::new()`].
+    pub fn with_card(mut self, card: Card) -> Self {
+        self.add_card(card);
+        self
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out_str = format!("{}[{}]", self.id, self.title);
+        // nest each card under the column
+        for card in &self.cards {
+            out_str += &format!("\n{}", card.to_string().indent(4));
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_column_preserves_card_order() {
+        // arrange
+        let wanted = concat!("todo[Todo]\n", "    b[Second]\n", "    a[First]");
+        // act
+        let got = Column::new("todo", "Todo")
+            .with_card(Card::new("b", "Second"))
+            .with_card(Card::new("a", "First"))
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+pub mod card;
+pub mod column;
+
+use crate::utils::Indent;
+use crate::MormaidError;
+pub use card::{Card, Priority};
+pub use column::Column;
+
+// ================================================================
+// Kanban struct and implementation
+// ================================================================
+
+/// Represents a mermaid `kanban` diagram.
+///
+/// Columns are rendered in the order they're added, each followed by its cards.
+/// Column titles must be unique, which is checked as each column is added.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::kanban::{Card, Column, Kanban};
+///
+/// let board = Kanban::new()
+///     .with_column(Column::new("todo", "Todo").with_card(Card::new("docs", "Write docs")))
+///     .unwrap();
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct Kanban {
+    pub columns: Vec<Column>,
+}
+
+impl Kanban {
+    /// The keyword that starts every mermaid kanban diagram.
+    pub const DIAGRAM_KEYWORD: &'static str = "kanban";
+
+    pub fn new() -> Self {
+        Kanban {
+            columns: Vec::new(),
+        }
+    }
+}
+
+// implement the Display trait
+impl fmt::Display for Kanban {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // initialize the kanban diagram
+        let mut out_str = Self::DIAGRAM_KEYWORD.to_string();
+        // append each column with its cards nested under it
+        for column in &self.columns {
+            out_str += &format!("\n{}", column.to_string().indent(4));
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+// ===========================================
+// Implement Kanban methods to manage columns
+// ===========================================
+impl Kanban {
+    /// Add a column to the right of the existing `Kanban.columns`.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::DuplicateColumn`], and doesn't add the column,
+    /// if the board already has a column with the same title.
+    pub fn try_add_column(&mut self, column: Column) -> Result<(), MormaidError> {
+        if self.get_column_by_title(&column.title).is_some() {
+            return Err(MormaidError::DuplicateColumn(column.title));
+        }
+        self.columns.push(column);
+        Ok(())
+    }
+
+    /// Add a column to the board on creation by chaining with [`Kanban::new()`].
+    ///
+    /// # Errors
+    /// Returns an error if the column's title is already used, see [`Kanban::try_add_column()`].
+    pub fn with_column(mut self, column: Column) -> Result<Self, MormaidError> {
+        self.try_add_column(column)?;
+        Ok(self)
+    }
+
+    /// Try to find a column on the board using its title.
+    #[must_use]
+    pub fn get_column_by_title(&self, title: &str) -> Option<&Column> {
+        self.columns.iter().find(|column| column.title == title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn display_two_columns_with_cards() {
+        // arrange
+        let wanted = concat!(
+            "kanban\n",
+            "    todo[Todo]\n",
+            "        docs[Write docs]@{ assigned: 'widal001' }\n",
+            "        blog[Write blog post]\n",
+            "    doing[In progress]\n",
+            "        parser[Write parser]@{ priority: 'High' }\n",
+            "        tests[Write tests]",
+        );
+        // act
+        let got = Kanban::new()
+            .with_column(
+                Column::new("todo", "Todo")
+                    .with_card(Card::new("docs", "Write docs").with_assigned("widal001"))
+                    .with_card(Card::new("blog", "Write blog post")),
+            )
+            .and_then(|board| {
+                board.with_column(
+                    Column::new("doing", "In progress")
+                        .with_card(
+                            Card::new("parser", "Write parser").with_priority(Priority::High),
+                        )
+                        .with_card(Card::new("tests", "Write tests")),
+                )
+            })
+            .expect("Expected unique column titles")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn add_column_with_duplicate_title_should_error() {
+        // arrange
+        let mut board = Kanban::new()
+            .with_column(Column::new("todo", "Todo"))
+            .expect("Expected a valid column");
+        // act
+        let got = board.try_add_column(Column::new("todo2", "Todo"));
+        // assert
+        assert_eq!(got, Err(MormaidError::DuplicateColumn("Todo".to_string())));
+        assert_eq!(board.columns.len(), 1);
+    }
+}
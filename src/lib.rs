@@ -2,10 +2,29 @@
 #![warn(clippy::style)]
 #![warn(clippy::perf)]
 #![warn(clippy::cargo)]
+// clippy 0.1.95 lints `format_push_string` under pedantic, but the diagrams are
+// built with `out_str += &format!(..)` throughout, as they were before it did
+#![allow(clippy::format_push_string)]
 
+pub mod c4;
+#[cfg(feature = "cli")]
+mod cli;
+pub mod diagram;
 pub mod erd;
+pub mod error;
+pub mod gitgraph;
+pub mod kanban;
+pub mod packet;
+pub mod quadrant;
+pub mod render;
+pub mod report;
 pub mod req;
-mod utils;
+pub mod testing;
+pub mod utils;
+pub mod xychart;
+
+pub use diagram::Diagram;
+pub use error::{MormaidError, ParseError, RenderError};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// Represents a range of bits in a packet diagram.
+///
+/// Both the start and end bits are inclusive, so a single bit field has
+/// the same start and end bit.
+#[must_use]
+#[derive(Debug, PartialEq)]
+pub struct Field {
+    pub start: u32,
+    pub end: u32,
+    pub label: String,
+}
+
+impl Field {
+    pub fn new(start: u32, end: u32, label: &str) -> Self {
+        Field {
+            start,
+            end,
+            label: label.to_string(),
+        }
+    }
+
+    /// Create a field that only spans a single bit.
+    pub fn bit(bit: u32, label: &str) -> Self {
+        Field::new(bit, bit, label)
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // single bit fields don't need a range
+        if self.start == self.end {
+            write!(f, "{}: \"{}\"", self.start, self.label)
+        } else {
+            write!(f, "{}-{}: \"{}\"", self.start, self.end, self.label)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_field() {
+        // act
+        let got = Field::new(0, 15, "Source Port");
+        // assert
+        assert_eq!(got.start, 0);
+        assert_eq!(got.end, 15);
+        assert_eq!(got.label, "Source Port");
+    }
+
+    #[test]
+    fn display_multi_bit_field() {
+        // act
+        let got = Field::new(16, 31, "Destination Port").to_string();
+        // assert
+        assert_eq!(got, "16-31: \"Destination Port\"");
+    }
+
+    #[test]
+    fn display_single_bit_field() {
+        // act
+        let got = Field::bit(106, "URG").to_string();
+        // assert
+        assert_eq!(got, "106: \"URG\"");
+    }
+}
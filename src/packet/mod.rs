@@ -0,0 +1,197 @@
+use std::fmt;
+
+pub mod field;
+
+use crate::MormaidError;
+pub use field::Field;
+
+// ================================================================
+// Packet struct and implementation
+// ================================================================
+
+/// Represents a mermaid `packet-beta` diagram.
+///
+/// Fields are stored in bit order and must cover a contiguous range of bits
+/// starting at bit 0, which is checked as each field is added.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::packet::{Field, Packet};
+///
+/// let mut udp = Packet::new().with_title("UDP Packet");
+/// udp.try_add_field(Field::new(0, 15, "Source Port")).unwrap();
+/// udp.try_add_field(Field::new(16, 31, "Destination Port")).unwrap();
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct Packet {
+    pub title: Option<String>,
+    pub fields: Vec<Field>,
+}
+
+impl Packet {
+    pub fn new() -> Self {
+        Packet {
+            title: None,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+}
+
+// implement the Display trait
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // initialize the packet diagram
+        let mut out_str = "packet-beta".to_string();
+        // append the title if it's populated
+        if let Some(title) = self.title.as_deref() {
+            out_str += &format!("\n    title {title}");
+        }
+        // append each field on its own indented line
+        for field in &self.fields {
+            out_str += &format!("\n    {field}");
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+// ===========================================
+// Implement Packet methods to manage fields
+// ===========================================
+impl Packet {
+    /// Add a field to the end of `Packet.fields`.
+    ///
+    /// # Errors
+    /// Returns an error, and doesn't add the field, if:
+    /// - the field ends before it starts ([`MormaidError::InvalidRange`])
+    /// - the field starts at or before the end of the last field ([`MormaidError::OverlappingRange`])
+    /// - the field leaves a gap after the last field, or the first field doesn't
+    ///   start at bit 0 ([`MormaidError::NonContiguousRange`])
+    pub fn try_add_field(&mut self, field: Field) -> Result<(), MormaidError> {
+        if field.end < field.start {
+            return Err(MormaidError::InvalidRange(field.label));
+        }
+        // the field must start on the bit after the end of the last field,
+        // and there's no bit after a field that ends on the last possible bit
+        let next_bit = match self.fields.last() {
+            Some(last) => last.end.checked_add(1),
+            None => Some(0),
+        };
+        let Some(next_bit) = next_bit else {
+            return Err(MormaidError::OverlappingRange(field.label));
+        };
+        if field.start < next_bit {
+            return Err(MormaidError::OverlappingRange(field.label));
+        }
+        if field.start > next_bit {
+            return Err(MormaidError::NonContiguousRange(field.label));
+        }
+        self.fields.push(field);
+        Ok(())
+    }
+
+    /// Add a field to the packet on creation by chaining with [`Packet::new()`].
+    ///
+    /// # Errors
+    /// Returns an error if the field isn't valid, see [`Packet::try_add_field()`].
+    pub fn with_field(mut self, field: Field) -> Result<Self, MormaidError> {
+        self.try_add_field(field)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn display_valid_packet_layout() {
+        // arrange
+        let wanted = concat!(
+            "packet-beta\n",
+            "    title UDP Packet\n",
+            "    0-15: \"Source Port\"\n",
+            "    16-31: \"Destination Port\"\n",
+            "    32-47: \"Length\"\n",
+            "    48-63: \"Checksum\"",
+        );
+        // act
+        let got = Packet::new()
+            .with_title("UDP Packet")
+            .with_field(Field::new(0, 15, "Source Port"))
+            .and_then(|packet| packet.with_field(Field::new(16, 31, "Destination Port")))
+            .and_then(|packet| packet.with_field(Field::new(32, 47, "Length")))
+            .and_then(|packet| packet.with_field(Field::new(48, 63, "Checksum")))
+            .expect("Expected a valid packet layout")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn add_field_after_last_possible_bit_should_error() {
+        // arrange
+        let mut packet = Packet::new()
+            .with_field(Field::new(0, u32::MAX, "Everything"))
+            .expect("Expected a valid field");
+        // act
+        let got = packet.try_add_field(Field::new(u32::MAX, u32::MAX, "Overflow"));
+        // assert
+        assert_eq!(
+            got,
+            Err(MormaidError::OverlappingRange("Overflow".to_string()))
+        );
+        assert_eq!(packet.fields.len(), 1);
+    }
+
+    #[test]
+    fn add_overlapping_field_should_error() {
+        // arrange
+        let mut packet = Packet::new()
+            .with_field(Field::new(0, 15, "Source Port"))
+            .expect("Expected a valid field");
+        // act
+        let got = packet.try_add_field(Field::new(8, 31, "Destination Port"));
+        // assert
+        assert_eq!(
+            got,
+            Err(MormaidError::OverlappingRange(
+                "Destination Port".to_string()
+            ))
+        );
+        assert_eq!(packet.fields.len(), 1);
+    }
+
+    #[test]
+    fn add_field_with_gap_should_error() {
+        // arrange
+        let mut packet = Packet::new();
+        // act
+        let got = packet.try_add_field(Field::new(1, 15, "Source Port"));
+        // assert
+        assert_eq!(
+            got,
+            Err(MormaidError::NonContiguousRange("Source Port".to_string()))
+        );
+    }
+
+    #[test]
+    fn add_field_ending_before_it_starts_should_error() {
+        // arrange
+        let mut packet = Packet::new();
+        // act
+        let got = packet.try_add_field(Field::new(15, 0, "Source Port"));
+        // assert
+        assert_eq!(
+            got,
+            Err(MormaidError::InvalidRange("Source Port".to_string()))
+        );
+    }
+}
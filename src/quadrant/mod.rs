@@ -0,0 +1,247 @@
+use std::fmt;
+
+pub mod point;
+
+use crate::{utils, MormaidError};
+pub use point::Point;
+
+// ==================================================================
+// Quadrant and Axis structs and implementation
+// ==================================================================
+
+/// The four quadrants of the chart, numbered counter-clockwise from the top right.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Quadrant {
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+impl Quadrant {
+    fn index(self) -> usize {
+        match self {
+            Quadrant::One => 0,
+            Quadrant::Two => 1,
+            Quadrant::Three => 2,
+            Quadrant::Four => 3,
+        }
+    }
+}
+
+/// The labels displayed at the low and (optionally) high end of an axis.
+#[must_use]
+pub struct Axis {
+    pub low: String,
+    pub high: Option<String>,
+}
+
+impl Axis {
+    pub fn new(low: &str, high: &str) -> Self {
+        Axis {
+            low: low.to_string(),
+            high: Some(high.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.high.as_deref() {
+            Some(high) => write!(f, "{} --> {high}", self.low),
+            None => write!(f, "{}", self.low),
+        }
+    }
+}
+
+// ================================================================
+// QuadrantChart struct and implementation
+// ================================================================
+
+/// Represents a mermaid `quadrantChart` diagram.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::quadrant::{Point, Quadrant, QuadrantChart};
+///
+/// let mut chart = QuadrantChart::new()
+///     .with_title("Reach and engagement")
+///     .with_x_axis("Low Reach", "High Reach")
+///     .with_y_axis("Low Engagement", "High Engagement")
+///     .with_quadrant_label(Quadrant::One, "We should expand");
+/// chart.try_add_point(Point::new("Campaign A", 0.3, 0.6)).unwrap();
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct QuadrantChart {
+    pub title: Option<String>,
+    pub x_axis: Option<Axis>,
+    pub y_axis: Option<Axis>,
+    pub quadrant_labels: [Option<String>; 4],
+    pub points: Vec<Point>,
+}
+
+impl QuadrantChart {
+    pub fn new() -> Self {
+        QuadrantChart {
+            title: None,
+            x_axis: None,
+            y_axis: None,
+            quadrant_labels: [None, None, None, None],
+            points: Vec::new(),
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn with_x_axis(mut self, low: &str, high: &str) -> Self {
+        self.x_axis = Some(Axis::new(low, high));
+        self
+    }
+
+    pub fn with_y_axis(mut self, low: &str, high: &str) -> Self {
+        self.y_axis = Some(Axis::new(low, high));
+        self
+    }
+
+    pub fn with_quadrant_label(mut self, quadrant: Quadrant, label: &str) -> Self {
+        self.quadrant_labels[quadrant.index()] = Some(label.to_string());
+        self
+    }
+}
+
+// implement the Display trait
+impl fmt::Display for QuadrantChart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // initialize the quadrantChart
+        let mut out_str = "quadrantChart".to_string();
+
+        // append the title and axes if they're populated
+        if let Some(title) = self.title.as_deref() {
+            out_str += &format!("\n    title {title}");
+        }
+        if let Some(axis) = &self.x_axis {
+            out_str += &format!("\n    x-axis {axis}");
+        }
+        if let Some(axis) = &self.y_axis {
+            out_str += &format!("\n    y-axis {axis}");
+        }
+
+        // append the quadrant labels that are populated
+        for (index, label) in self.quadrant_labels.iter().enumerate() {
+            if let Some(label) = label.as_deref() {
+                out_str += &format!("\n    quadrant-{} {label}", index + 1);
+            }
+        }
+
+        // append points if the chart has them
+        if !self.points.is_empty() {
+            out_str = utils::append_items(out_str, &self.points, "Points", 4);
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+// ==============================================
+// Implement QuadrantChart methods to manage points
+// ==============================================
+impl QuadrantChart {
+    /// Add a point to `QuadrantChart.points`.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::InvalidCoordinate`] if either of the point's
+    /// coordinates is outside of the 0 to 1 range, in which case the point isn't added.
+    pub fn try_add_point(&mut self, point: Point) -> Result<(), MormaidError> {
+        if !point.is_in_range() {
+            return Err(MormaidError::InvalidCoordinate(point.name));
+        }
+        self.points.push(point);
+        Ok(())
+    }
+
+    /// Add a point to the chart on creation by chaining with [`QuadrantChart::new()`].
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::InvalidCoordinate`] if either of the point's
+    /// coordinates is outside of the 0 to 1 range.
+    pub fn with_point(mut self, point: Point) -> Result<Self, MormaidError> {
+        self.try_add_point(point)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn add_point_in_range() {
+        // arrange
+        let mut chart = QuadrantChart::new();
+        // act
+        let got = chart.try_add_point(Point::new("A", 0.3, 0.6));
+        // assert
+        assert_eq!(got, Ok(()));
+        assert_eq!(chart.points.len(), 1);
+    }
+
+    #[test]
+    fn add_point_out_of_range_should_error() {
+        // arrange
+        let mut chart = QuadrantChart::new();
+        // act
+        let got = chart.try_add_point(Point::new("A", 1.5, 0.6));
+        // assert
+        assert_eq!(got, Err(MormaidError::InvalidCoordinate("A".to_string())));
+        assert!(chart.points.is_empty());
+    }
+
+    #[test]
+    fn display_empty_chart() {
+        // arrange
+        let wanted = "quadrantChart";
+        // act
+        let got = QuadrantChart::new().to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_chart_with_all_components() {
+        // arrange
+        let wanted = concat!(
+            "quadrantChart\n",
+            "    title Prioritization\n",
+            "    x-axis Low Effort --> High Effort\n",
+            "    y-axis Low Impact --> High Impact\n",
+            "    quadrant-1 Plan\n",
+            "    quadrant-2 Do now\n",
+            "    quadrant-3 Delegate\n",
+            "    quadrant-4 Drop\n",
+            "    %% Points start\n",
+            "    Search: [0.25, 0.75]\n",
+            "    Export: [0.8, 0.1]\n",
+            "    %% Points end",
+        );
+        // act
+        let got = QuadrantChart::new()
+            .with_title("Prioritization")
+            .with_x_axis("Low Effort", "High Effort")
+            .with_y_axis("Low Impact", "High Impact")
+            .with_quadrant_label(Quadrant::One, "Plan")
+            .with_quadrant_label(Quadrant::Two, "Do now")
+            .with_quadrant_label(Quadrant::Three, "Delegate")
+            .with_quadrant_label(Quadrant::Four, "Drop")
+            .with_point(Point::new("Search", 0.25, 0.75))
+            .and_then(|chart| chart.with_point(Point::new("Export", 0.8, 0.1)))
+            .expect("Expected points to be added")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// Represents a point plotted on a quadrant chart.
+///
+/// Both coordinates must be between 0 and 1 (inclusive) to be plotted, which is
+/// checked when the point is added with [`super::QuadrantChart::try_add_point()`].
+#[must_use]
+#[derive(Debug, PartialEq)]
+pub struct Point {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(name: &str, x: f64, y: f64) -> Self {
+        Point {
+            name: name.to_string(),
+            x,
+            y,
+        }
+    }
+
+    /// Check whether both of the point's coordinates are between 0 and 1.
+    #[must_use]
+    pub fn is_in_range(&self) -> bool {
+        (0.0..=1.0).contains(&self.x) && (0.0..=1.0).contains(&self.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: [{}, {}]", self.name, self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAME: &str = "Campaign A";
+
+    #[test]
+    fn create_point() {
+        // act
+        let got = Point::new(NAME, 0.3, 0.6);
+        // assert
+        assert_eq!(got.name, NAME);
+        assert!((got.x - 0.3).abs() < f64::EPSILON);
+        assert!((got.y - 0.6).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn points_on_the_boundary_are_in_range() {
+        // assert
+        assert!(Point::new(NAME, 0.0, 1.0).is_in_range());
+        assert!(!Point::new(NAME, 1.1, 0.5).is_in_range());
+        assert!(!Point::new(NAME, 0.5, -0.1).is_in_range());
+    }
+
+    #[test]
+    fn display_point() {
+        // arrange
+        let wanted = "Campaign A: [0.3, 0.6]";
+        // act
+        let got = Point::new(NAME, 0.3, 0.6).to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+}
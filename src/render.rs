@@ -0,0 +1,200 @@
+// ==================================================================
+// LineEnding enum and implementation
+// ==================================================================
+
+/// The characters used to separate the lines of a rendered diagram.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LineEnding {
+    /// Separate lines with `\n`, which is the default.
+    #[default]
+    Lf,
+    /// Separate lines with `\r\n`, which some Windows toolchains expect.
+    Crlf,
+}
+
+impl LineEnding {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+
+    // Replace the `\n` separators in a rendered diagram with this line ending,
+    // keeping a trailing newline if there is one. Rendered output only ever
+    // contains `\n`, so there's no `\r\n` to double up.
+    pub(crate) fn apply(self, rendered: &str) -> String {
+        match self {
+            LineEnding::Lf => rendered.to_string(),
+            LineEnding::Crlf => rendered.replace('\n', self.as_str()),
+        }
+    }
+}
+
+// ==================================================================
+// EscapeStyle enum
+// ==================================================================
+
+/// How quotes and other special characters are escaped in quoted strings,
+/// e.g. labels, comments, and requirement text.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum EscapeStyle {
+    /// Mermaid's entity codes, e.g. `#quot;` and `#35;`, which is the default.
+    #[default]
+    Entity,
+    /// HTML entities, e.g. `&quot;` and `&amp;`.
+    Html,
+    /// Backslash escapes, e.g. `\"` and `\\`.
+    Backslash,
+}
+
+// ==================================================================
+// RenderOptions struct and implementation
+// ==================================================================
+
+/// Options that control how a diagram is rendered to mermaid syntax.
+///
+/// The default options produce the same output as the diagram's `Display`
+/// implementation.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::erd::ERD;
+/// # use crate::mormaid::render::{LineEnding, RenderOptions};
+///
+/// let options = RenderOptions::new().with_line_ending(LineEnding::Crlf);
+/// let rendered = ERD::new().render_with(&options);
+/// ```
+#[must_use]
+#[derive(Debug, Default, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct RenderOptions {
+    pub line_ending: LineEnding,
+    /// Omit the `%% ... start` and `%% ... end` comments around each section.
+    pub compact: bool,
+    /// Replace the keyword that starts the diagram, e.g. `erDiagram`.
+    ///
+    /// By default each diagram uses its own `DIAGRAM_KEYWORD`.
+    pub keyword: Option<String>,
+    /// Group an ERD's relationships by their left entity, with a comment before each group.
+    pub group_relationships: bool,
+    /// Emit `risk: Low` for requirements without a risk, matching mermaid's implicit default.
+    pub explicit_default_risk: bool,
+    /// Precede each element, requirement, and relationship of a requirement diagram
+    /// with a `%% [n]` comment, numbering each section from 1.
+    pub number_items: bool,
+    /// Render a requirement's `text` before its `risk`, the order used in mermaid's docs.
+    pub text_before_risk: bool,
+    /// Precede each ERD entity that was created by a relationship with `%% auto-created`.
+    pub mark_auto_created: bool,
+    /// How special characters are escaped in quoted strings.
+    pub escape_style: EscapeStyle,
+    /// Group a requirement diagram's requirements by type, with a comment before each group.
+    pub group_requirements: bool,
+    /// Pad the left entity id of each ERD relationship so the glyphs line up in a column.
+    pub align_relationships: bool,
+}
+
+impl RenderOptions {
+    pub fn new() -> Self {
+        RenderOptions::default()
+    }
+
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    pub fn with_keyword(mut self, keyword: &str) -> Self {
+        self.keyword = Some(keyword.to_string());
+        self
+    }
+
+    pub fn with_group_relationships(mut self, group_relationships: bool) -> Self {
+        self.group_relationships = group_relationships;
+        self
+    }
+
+    pub fn with_explicit_default_risk(mut self, explicit_default_risk: bool) -> Self {
+        self.explicit_default_risk = explicit_default_risk;
+        self
+    }
+
+    pub fn with_number_items(mut self, number_items: bool) -> Self {
+        self.number_items = number_items;
+        self
+    }
+
+    pub fn with_text_before_risk(mut self, text_before_risk: bool) -> Self {
+        self.text_before_risk = text_before_risk;
+        self
+    }
+
+    pub fn with_mark_auto_created(mut self, mark_auto_created: bool) -> Self {
+        self.mark_auto_created = mark_auto_created;
+        self
+    }
+
+    pub fn with_escape_style(mut self, escape_style: EscapeStyle) -> Self {
+        self.escape_style = escape_style;
+        self
+    }
+
+    pub fn with_group_requirements(mut self, group_requirements: bool) -> Self {
+        self.group_requirements = group_requirements;
+        self
+    }
+
+    pub fn with_align_relationships(mut self, align_relationships: bool) -> Self {
+        self.align_relationships = align_relationships;
+        self
+    }
+
+    // Get the keyword override, falling back to the diagram's default keyword
+    pub(crate) fn keyword_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.keyword.as_deref().unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENDERED: &str = "erDiagram\n    ALBUM\n    SONG";
+
+    #[test]
+    fn lf_line_endings_are_unchanged() {
+        // act
+        let got = LineEnding::Lf.apply(RENDERED);
+        // assert
+        assert_eq!(got, RENDERED);
+    }
+
+    #[test]
+    fn crlf_line_endings_replace_every_newline() {
+        // arrange
+        let wanted = "erDiagram\r\n    ALBUM\r\n    SONG";
+        // act
+        let got = LineEnding::Crlf.apply(RENDERED);
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn crlf_line_endings_keep_a_trailing_newline() {
+        // arrange
+        let rendered = "erDiagram\n    ALBUM\n";
+        let wanted = "erDiagram\r\n    ALBUM\r\n";
+        // act
+        let got = LineEnding::Crlf.apply(rendered);
+        // assert
+        assert_eq!(got, wanted);
+    }
+}
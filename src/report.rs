@@ -0,0 +1,125 @@
+// ==================================================================
+// MarkdownReport struct and implementation
+// ==================================================================
+
+use std::fmt;
+
+use crate::diagram::Diagram;
+
+/// A markdown document with each diagram in its own fenced block under a heading.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::erd::ERD;
+/// # use crate::mormaid::report::MarkdownReport;
+///
+/// let erd = ERD::new();
+/// let report = MarkdownReport::new()
+///     .with_title("Schema")
+///     .with_section("Tables", &erd)
+///     .to_string();
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct MarkdownReport<'a> {
+    pub title: Option<String>,
+    pub sections: Vec<(String, &'a dyn Diagram)>,
+}
+
+impl<'a> MarkdownReport<'a> {
+    pub fn new() -> Self {
+        MarkdownReport {
+            title: None,
+            sections: Vec::new(),
+        }
+    }
+
+    /// Add a top-level `#` heading to the start of the report.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Add a diagram under a `##` heading to the end of the report.
+    pub fn add_section(&mut self, heading: &str, diagram: &'a dyn Diagram) {
+        self.sections.push((heading.to_string(), diagram));
+    }
+
+    /// Add a section to the report on creation by chaining with [`MarkdownReport::new()`].
+    pub fn with_section(mut self, heading: &str, diagram: &'a dyn Diagram) -> Self {
+        self.add_section(heading, diagram);
+        self
+    }
+}
+
+impl fmt::Display for MarkdownReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut blocks = Vec::new();
+        if let Some(title) = self.title.as_deref() {
+            blocks.push(format!("# {title}"));
+        }
+        // separate each heading and diagram with a blank line
+        for (heading, diagram) in &self.sections {
+            blocks.push(format!("## {heading}\n\n{}", diagram.to_markdown()));
+        }
+        write!(f, "{}", blocks.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erd::{Entity, ERD};
+    use crate::req::{Requirement, RequirementDiagram, RequirementType};
+
+    #[test]
+    fn report_with_erd_and_requirement_diagram() {
+        // arrange
+        let erd = ERD::new().with_entity(Entity::new("ALBUM"));
+        let req = RequirementDiagram::new().with_requirement(Requirement::new(
+            RequirementType::Default,
+            "milestone",
+            "1",
+        ));
+        let wanted = concat!(
+            "# Music library\n",
+            "\n",
+            "## Schema\n",
+            "\n",
+            "```mermaid\n",
+            "erDiagram\n",
+            "    %% Entities start\n",
+            "    ALBUM\n",
+            "    %% Entities end\n",
+            "```\n",
+            "\n",
+            "## Requirements\n",
+            "\n",
+            "```mermaid\n",
+            "requirementDiagram\n",
+            "    %% Requirements start\n",
+            "    requirement milestone {\n",
+            "        id: 1\n",
+            "    }\n",
+            "    %% Requirements end\n",
+            "```",
+        );
+        // act
+        let got = MarkdownReport::new()
+            .with_title("Music library")
+            .with_section("Schema", &erd)
+            .with_section("Requirements", &req)
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn empty_report_is_empty() {
+        // act
+        let got = MarkdownReport::new().to_string();
+        // assert
+        assert_eq!(got, "");
+    }
+}
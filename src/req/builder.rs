@@ -0,0 +1,125 @@
+use super::{Element, Relationship, RelationshipType, Requirement, RequirementDiagram};
+
+/// A reference to an element or requirement that was added to a [`RequirementDiagramBuilder`].
+///
+/// Handles can only be created by the builder, so a relationship built from
+/// two handles always references elements or requirements that exist.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    name: String,
+}
+
+impl NodeHandle {
+    /// The name of the element or requirement the handle references.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Builds a [`RequirementDiagram`] whose relationships can't reference missing endpoints.
+///
+/// Unlike [`RequirementDiagram::add_relationship()`], which panics at runtime if
+/// an endpoint is missing, relationships are added using the handles returned
+/// when elements and requirements are added to the builder.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::req::{
+/// #     Element, RelationshipType, Requirement, RequirementDiagramBuilder, RequirementType,
+/// # };
+///
+/// let mut builder = RequirementDiagramBuilder::new();
+/// let search = builder.element(Element::new("search", "release"));
+/// let feature = builder.requirement(Requirement::new(
+///     RequirementType::Functional,
+///     "feature_1",
+///     "1.1.1",
+/// ));
+/// builder.relationship(&search, &feature, RelationshipType::Satisfies);
+/// let diagram = builder.build();
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct RequirementDiagramBuilder {
+    diagram: RequirementDiagram,
+}
+
+impl RequirementDiagramBuilder {
+    pub fn new() -> Self {
+        RequirementDiagramBuilder {
+            diagram: RequirementDiagram::new(),
+        }
+    }
+
+    /// Add an element to the diagram and return a handle to it.
+    pub fn element(&mut self, element: Element) -> NodeHandle {
+        let name = element.name.clone();
+        self.diagram.add_element(element);
+        NodeHandle { name }
+    }
+
+    /// Add a requirement to the diagram and return a handle to it.
+    pub fn requirement(&mut self, req: Requirement) -> NodeHandle {
+        let name = req.name.clone();
+        self.diagram.add_requirement(req);
+        NodeHandle { name }
+    }
+
+    /// Add a relationship between two elements or requirements already added to the builder.
+    ///
+    /// Handles should only be used with the builder that created them.
+    pub fn relationship(
+        &mut self,
+        source: &NodeHandle,
+        target: &NodeHandle,
+        kind: RelationshipType,
+    ) -> &mut Self {
+        let relationship = Relationship::new(source.name(), target.name(), kind);
+        self.diagram.relationships.push(relationship);
+        self
+    }
+
+    /// Finish building and return the diagram.
+    pub fn build(self) -> RequirementDiagram {
+        self.diagram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::req::RequirementType;
+
+    #[test]
+    fn build_diagram_with_handles() {
+        // arrange
+        let mut builder = RequirementDiagramBuilder::new();
+        let search = builder.element(Element::new("search", "release"));
+        let feature = builder.requirement(Requirement::new(
+            RequirementType::Functional,
+            "feature_1",
+            "1.1.1",
+        ));
+        let speed = builder.requirement(Requirement::new(
+            RequirementType::Performance,
+            "speed",
+            "1.1.2",
+        ));
+        // act
+        builder
+            .relationship(&search, &feature, RelationshipType::Satisfies)
+            .relationship(&feature, &speed, RelationshipType::Derives);
+        let diagram = builder.build();
+        // assert
+        assert_eq!(search.name(), "search");
+        assert_eq!(diagram.elements.len(), 1);
+        assert_eq!(diagram.requirements.len(), 2);
+        assert_eq!(diagram.relationships.len(), 2);
+        assert!(diagram
+            .to_string()
+            .contains("    search - satisfies -> feature_1\n    feature_1 - derives -> speed"));
+    }
+}
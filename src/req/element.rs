@@ -1,5 +1,46 @@
 use std::fmt;
 
+use crate::MormaidError;
+
+// ==================================================================
+// ElementKind enum
+// ==================================================================
+
+/// Common values for the type of an element.
+///
+/// Mermaid doesn't constrain the type of an element, so [`Element::new()`]
+/// still accepts any string, but [`Element::typed()`] avoids typos in the usual ones.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ElementKind {
+    Brief,
+    Document,
+    Feature,
+    Component,
+    Service,
+    Simulation,
+    TestSuite,
+}
+
+impl fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind_str = match self {
+            ElementKind::Brief => "brief",
+            ElementKind::Document => "document",
+            ElementKind::Feature => "feature",
+            ElementKind::Component => "component",
+            ElementKind::Service => "service",
+            ElementKind::Simulation => "simulation",
+            ElementKind::TestSuite => "test suite",
+        };
+        write!(f, "{kind_str}")
+    }
+}
+
+// ==================================================================
+// Element struct and implementation
+// ==================================================================
+
 #[must_use]
 pub struct Element {
     pub name: String,
@@ -16,10 +57,59 @@ impl Element {
         }
     }
 
+    /// Create an element whose type is one of the common [`ElementKind`] values.
+    pub fn typed(kind: ElementKind, name: &str) -> Self {
+        Element::new(name, &kind.to_string())
+    }
+
+    /// Chain with `Element::new()` to replace the type with an [`ElementKind`].
+    pub fn with_kind(mut self, kind: ElementKind) -> Self {
+        self.kind = kind.to_string();
+        self
+    }
+
+    /// Chain with `Element::new()` to add a docref without validating it.
     pub fn with_docref(mut self, docref: &str) -> Self {
         self.docref = Some(docref.to_string());
         self
     }
+
+    /// Chain with `Element::new()` to add a docref that's a valid URI,
+    /// e.g. `https://example.com/spec`, or a relative reference, e.g. `reqs/test_entity.py`.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::InvalidDocref`] if the docref is empty, contains
+    /// whitespace or characters that aren't allowed in a URI, or starts with a
+    /// malformed scheme.
+    pub fn try_with_docref(self, docref: &str) -> Result<Self, MormaidError> {
+        if is_valid_docref(docref) {
+            Ok(self.with_docref(docref))
+        } else {
+            Err(MormaidError::InvalidDocref(docref.to_string()))
+        }
+    }
+}
+
+// Check that a docref is a URI or relative reference, without resolving it
+fn is_valid_docref(docref: &str) -> bool {
+    let has_valid_chars = !docref.is_empty()
+        && docref
+            .chars()
+            .all(|c| !c.is_whitespace() && !c.is_control() && !"\"<>\\^`{|}".contains(c));
+    if !has_valid_chars {
+        return false;
+    }
+    // a colon before the first slash separates a scheme from the rest of the URI
+    let first_slash = docref.find('/').unwrap_or(docref.len());
+    match docref[..first_slash].split_once(':') {
+        Some((scheme, rest)) => {
+            let mut chars = scheme.chars();
+            chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+                && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                && !(rest.is_empty() && first_slash == docref.len())
+        }
+        None => true,
+    }
 }
 
 impl fmt::Display for Element {
@@ -98,4 +188,73 @@ mod tests {
         // assert
         assert_eq!(got, wanted);
     }
+
+    #[test]
+    fn display_each_element_kind() {
+        // arrange
+        let kinds = [
+            (ElementKind::Brief, "brief"),
+            (ElementKind::Document, "document"),
+            (ElementKind::Feature, "feature"),
+            (ElementKind::Component, "component"),
+            (ElementKind::Service, "service"),
+            (ElementKind::Simulation, "simulation"),
+            (ElementKind::TestSuite, "test suite"),
+        ];
+        for (kind, kind_str) in kinds {
+            let wanted = format!("element {NAME} {{\n    type: \"{kind_str}\"\n}}");
+            // act
+            let got = Element::typed(kind, NAME).to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
+    }
+
+    #[test]
+    fn typed_and_string_kinds_match() {
+        // act
+        let typed = Element::typed(ElementKind::Brief, NAME);
+        let string = Element::new(NAME, "brief");
+        let replaced = Element::new(NAME, KIND).with_kind(ElementKind::Brief);
+        // assert
+        assert_eq!(typed.kind, string.kind);
+        assert_eq!(replaced.to_string(), string.to_string());
+    }
+
+    #[test]
+    fn try_with_valid_docrefs() {
+        for docref in [
+            DOCREF,
+            "reqs/test_entity.py",
+            "mailto:team@example.com",
+            "#section-2",
+        ] {
+            // act
+            let element = Element::new(NAME, KIND).try_with_docref(docref);
+            // assert
+            assert_eq!(
+                element.map(|element| element.docref),
+                Ok(Some(docref.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn try_with_invalid_docrefs_should_error() {
+        for docref in [
+            "",
+            "see the spec",
+            "https:",
+            "1http://example.com",
+            "<spec>",
+        ] {
+            // act
+            let got = Element::new(NAME, KIND).try_with_docref(docref);
+            // assert
+            assert_eq!(
+                got.map(|element| element.docref),
+                Err(MormaidError::InvalidDocref(docref.to_string()))
+            );
+        }
+    }
 }
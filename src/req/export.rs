@@ -0,0 +1,68 @@
+// ==================================================================
+// Implement RequirementDiagram methods to export the diagram to other formats
+// ==================================================================
+
+use super::{Requirement, RequirementDiagram};
+
+impl RequirementDiagram {
+    /// Render a markdown table summarizing each requirement in the diagram.
+    ///
+    /// The table has one row per requirement, sorted by name, with its name,
+    /// id, type, risk, verify method, and text. Fields that aren't set are left
+    /// blank, and pipes and line breaks in the text are escaped so the table
+    /// stays intact.
+    #[must_use]
+    pub fn to_summary_table(&self) -> String {
+        let mut requirements: Vec<&Requirement> = self.requirements.values().collect();
+        requirements.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut table = String::from("| Name | Id | Type | Risk | Verify method | Text |\n");
+        table += "| --- | --- | --- | --- | --- | --- |";
+        for req in requirements {
+            let cells = [
+                req.name.clone(),
+                req.id.clone(),
+                req.kind.to_string(),
+                req.risk
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                req.verify_method
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                req.text.clone().unwrap_or_default(),
+            ]
+            .map(|cell| cell.replace('|', "\\|").replace('\n', "<br/>"));
+            table += &format!("\n| {} |", cells.join(" | "));
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn summary_table_has_one_row_per_requirement() {
+        // arrange
+        let diagram = RequirementDiagram::new()
+            .with_requirement(
+                Requirement::new(RequirementType::Performance, "latency", "2")
+                    .with_risk(Risk::High)
+                    .with_verify_method(VerifyMethod::Test)
+                    .with_text("Respond in < 200ms | p99"),
+            )
+            .with_requirement(Requirement::new(RequirementType::Functional, "login", "1"));
+        let wanted = concat!(
+            "| Name | Id | Type | Risk | Verify method | Text |\n",
+            "| --- | --- | --- | --- | --- | --- |\n",
+            "| latency | 2 | performanceRequirement | High | Test | Respond in < 200ms \\| p99 |\n",
+            "| login | 1 | functionalRequirement |  |  |  |",
+        );
+        // act
+        let got = diagram.to_summary_table();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+}
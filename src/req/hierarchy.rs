@@ -0,0 +1,132 @@
+// ==================================================================
+// Implement RequirementDiagram methods to check the requirement hierarchy
+// ==================================================================
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::{RelationshipType, RequirementDiagram};
+
+// Whether a node is still being explored or all of its descendants have been checked
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+impl RequirementDiagram {
+    /// Find the cycles in the hierarchy formed by `contains`, `derives`, and
+    /// `refines` relationships.
+    ///
+    /// A hierarchy should be acyclic, so each cycle is a modeling error, e.g.
+    /// `a` contains `b` and `b` contains `a`. The relationships are treated as
+    /// edges from source to target, and each cycle is listed as the names along
+    /// it, starting from the first one visited, e.g. `["a", "b"]`. A relationship
+    /// from a node to itself is a cycle of one. Nodes are visited in name order,
+    /// so the result is deterministic, but a node that's part of several
+    /// overlapping cycles is only reported in the first one found.
+    #[must_use]
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let mut graph: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for r in &self.relationships {
+            if matches!(
+                r.kind,
+                RelationshipType::Contains | RelationshipType::Derives | RelationshipType::Refines
+            ) {
+                graph.entry(&r.source).or_default().push(&r.target);
+                graph.entry(&r.target).or_default();
+            }
+        }
+        let mut visits = HashMap::new();
+        let mut path = Vec::new();
+        let mut cycles = Vec::new();
+        for node in graph.keys() {
+            if !visits.contains_key(node) {
+                visit(node, &graph, &mut visits, &mut path, &mut cycles);
+            }
+        }
+        cycles
+    }
+}
+
+// Walk the graph depth first, recording a cycle whenever an edge leads back into the current path
+fn visit<'a>(
+    node: &'a str,
+    graph: &BTreeMap<&'a str, Vec<&'a str>>,
+    visits: &mut HashMap<&'a str, Visit>,
+    path: &mut Vec<&'a str>,
+    cycles: &mut Vec<Vec<String>>,
+) {
+    visits.insert(node, Visit::InProgress);
+    path.push(node);
+    for &next in &graph[node] {
+        match visits.get(next) {
+            None => visit(next, graph, visits, path, cycles),
+            Some(Visit::InProgress) => {
+                let start = path.iter().position(|&n| n == next).unwrap_or_default();
+                cycles.push(path[start..].iter().map(ToString::to_string).collect());
+            }
+            Some(Visit::Done) => {}
+        }
+    }
+    path.pop();
+    visits.insert(node, Visit::Done);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn requirement(name: &str) -> Requirement {
+        Requirement::new(RequirementType::Functional, name, name)
+    }
+
+    #[test]
+    fn acyclic_hierarchy_has_no_cycles() {
+        // arrange
+        let diagram = RequirementDiagram::new()
+            .with_requirement(requirement("system"))
+            .with_requirement(requirement("login"))
+            .with_requirement(requirement("mfa"))
+            .with_requirement(requirement("audit"))
+            .with_relationship(Relationship::new(
+                "system",
+                "login",
+                RelationshipType::Contains,
+            ))
+            .with_relationship(Relationship::new("login", "mfa", RelationshipType::Derives))
+            .with_relationship(Relationship::new(
+                "system",
+                "mfa",
+                RelationshipType::Refines,
+            ))
+            // satisfies isn't part of the hierarchy, so it can't close a cycle
+            .with_relationship(Relationship::new(
+                "mfa",
+                "system",
+                RelationshipType::Satisfies,
+            ))
+            .with_relationship(Relationship::new(
+                "audit",
+                "system",
+                RelationshipType::Contains,
+            ));
+        // act
+        let got = diagram.find_cycles();
+        // assert
+        assert!(got.is_empty(), "Expected no cycles, got {got:?}");
+    }
+
+    #[test]
+    fn find_two_node_cycle() {
+        // arrange
+        let diagram = RequirementDiagram::new()
+            .with_requirement(requirement("a"))
+            .with_requirement(requirement("b"))
+            .with_relationship(Relationship::new("a", "b", RelationshipType::Contains))
+            .with_relationship(Relationship::new("b", "a", RelationshipType::Contains));
+        // act
+        let got = diagram.find_cycles();
+        // assert
+        assert_eq!(got, vec![vec!["a".to_string(), "b".to_string()]]);
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// A hierarchical, dotted requirement id like `1`, `1.1`, or `1.1.1`.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::req::{ReqId, Requirement, RequirementType};
+///
+/// let parent = ReqId::new(1);
+/// let first = parent.child(1); // 1.1
+/// let second = first.next_sibling(); // 1.2
+/// let req = Requirement::new(RequirementType::Functional, "search", &second.to_string());
+/// ```
+#[must_use]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ReqId(Vec<u32>);
+
+impl ReqId {
+    /// Create a top-level id, e.g. `ReqId::new(1)` is `1`.
+    pub fn new(index: u32) -> Self {
+        ReqId(vec![index])
+    }
+
+    /// Create the id of a child nested one level below this id, e.g. `1.1` is a child of `1`.
+    pub fn child(&self, index: u32) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(index);
+        ReqId(segments)
+    }
+
+    /// Create the id that follows this one at the same level, e.g. `1.2` follows `1.1`.
+    pub fn next_sibling(&self) -> Self {
+        let mut segments = self.0.clone();
+        if let Some(last) = segments.last_mut() {
+            *last += 1;
+        }
+        ReqId(segments)
+    }
+}
+
+impl fmt::Display for ReqId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segments: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", segments.join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_top_level_id() {
+        // act
+        let got = ReqId::new(1).to_string();
+        // assert
+        assert_eq!(got, "1");
+    }
+
+    #[test]
+    fn generate_child_id() {
+        // act
+        let got = ReqId::new(1).child(1).child(3);
+        // assert
+        assert_eq!(got.to_string(), "1.1.3");
+    }
+
+    #[test]
+    fn generate_next_sibling_id() {
+        // arrange
+        let id = ReqId::new(2).child(9);
+        // act
+        let got = id.next_sibling();
+        // assert
+        assert_eq!(got.to_string(), "2.10");
+        assert_eq!(id.to_string(), "2.9");
+    }
+}
@@ -1,13 +1,21 @@
 use std::collections::HashMap;
 use std::fmt;
 
+pub mod builder;
 pub mod element;
+mod export;
+mod hierarchy;
+pub mod id;
+mod parse;
 pub mod relationship;
 pub mod requirement;
 
-use crate::utils;
-pub use element::Element;
-pub use relationship::{Relationship, RelationshipType};
+use crate::render::RenderOptions;
+use crate::{utils, MormaidError};
+pub use builder::{NodeHandle, RequirementDiagramBuilder};
+pub use element::{Element, ElementKind};
+pub use id::ReqId;
+pub use relationship::{Connector, Relationship, RelationshipType};
 pub use requirement::{Requirement, RequirementType, Risk, VerifyMethod};
 
 #[must_use]
@@ -16,15 +24,99 @@ pub struct RequirementDiagram {
     pub requirements: HashMap<String, Requirement>,
     pub elements: HashMap<String, Element>,
     pub relationships: Vec<Relationship>,
+    /// Color requirements by their risk using `classDef` and `class` lines.
+    /// See [`RequirementDiagram::with_risk_styling()`].
+    pub risk_styling: bool,
+    /// Free text documenting the diagram, see [`RequirementDiagram::with_annotation()`].
+    pub annotations: Vec<String>,
+    /// The accessible title read by screen readers, see [`RequirementDiagram::with_acc_title()`].
+    pub acc_title: Option<String>,
+    /// The accessible description read by screen readers, see [`RequirementDiagram::with_acc_descr()`].
+    pub acc_descr: Option<String>,
 }
 impl RequirementDiagram {
+    /// The keyword that starts every mermaid requirement diagram.
+    pub const DIAGRAM_KEYWORD: &'static str = "requirementDiagram";
+
     pub fn new() -> Self {
         RequirementDiagram {
             requirements: HashMap::new(),
             elements: HashMap::new(),
             relationships: Vec::new(),
+            risk_styling: false,
+            annotations: Vec::new(),
+            acc_title: None,
+            acc_descr: None,
         }
     }
+
+    /// Chain with [`RequirementDiagram::new()`] to add an accessible title for screen readers.
+    ///
+    /// The title is rendered as an `accTitle:` line right after the `requirementDiagram` keyword.
+    pub fn with_acc_title(mut self, title: &str) -> Self {
+        self.acc_title = Some(title.to_string());
+        self
+    }
+
+    /// Chain with [`RequirementDiagram::new()`] to add an accessible description for screen readers.
+    ///
+    /// The description is rendered as an `accDescr:` line after the keyword
+    /// (and the `accTitle`, if there is one), or as an `accDescr { ... }` block
+    /// if it spans several lines.
+    pub fn with_acc_descr(mut self, descr: &str) -> Self {
+        self.acc_descr = Some(descr.to_string());
+        self
+    }
+
+    /// Chain with [`RequirementDiagram::new()`] to document the diagram with free text.
+    ///
+    /// Annotations are rendered as `%%` comment lines before the elements, so
+    /// they're preserved in the diagram source but not displayed. They aren't
+    /// elements or requirements, so they can't be the endpoint of a relationship.
+    pub fn with_annotation(mut self, text: &str) -> Self {
+        self.annotations.push(text.to_string());
+        self
+    }
+
+    /// Chain with [`RequirementDiagram::new()`] to color requirements by their risk.
+    ///
+    /// When enabled, a `classDef` is rendered after the diagram body for each
+    /// level of risk, followed by a `class` line assigning each requirement
+    /// with a risk to the matching class. Requirements without a risk are unstyled.
+    pub fn with_risk_styling(mut self) -> Self {
+        self.risk_styling = true;
+        self
+    }
+}
+
+// Get the name of the class used to style requirements with this risk
+fn risk_class(risk: &Risk) -> &'static str {
+    match risk {
+        Risk::High => "highRisk",
+        Risk::Medium => "mediumRisk",
+        Risk::Low => "lowRisk",
+    }
+}
+
+// Precede each item with a `%% [n]` comment (if requested) so it can be cross-referenced,
+// skipping comments such as group headers so the numbers stay contiguous
+fn number_items<T, I>(items: T, options: &RenderOptions) -> Vec<String>
+where
+    T: IntoIterator<Item = I>,
+    I: fmt::Display,
+{
+    let mut number = 0;
+    items
+        .into_iter()
+        .map(|item| {
+            let item = item.to_string();
+            if !options.number_items || item.starts_with("%%") {
+                return item;
+            }
+            number += 1;
+            format!("%% [{number}]\n{item}")
+        })
+        .collect()
 }
 
 // ============================================================
@@ -33,7 +125,7 @@ impl RequirementDiagram {
 impl RequirementDiagram {
     /// Add an element to `RequirementDiagram.elements`, keyed by the element's name.
     pub fn add_element(&mut self, element: Element) {
-        self.elements.insert(element.name.to_string(), element);
+        self.elements.insert(element.name.clone(), element);
     }
 
     /// Add an element to the `RequirementDiagram` on creation by chaining with [`RequirementDiagram::new()`].
@@ -42,34 +134,166 @@ impl RequirementDiagram {
         self
     }
 
+    /// The number of elements in the `RequirementDiagram`.
+    #[must_use]
+    pub fn element_count(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Check if the `RequirementDiagram` has no elements, requirements, or relationships.
+    ///
+    /// Render flags like `risk_styling` aren't content, so they don't count.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty() && self.requirements.is_empty() && self.relationships.is_empty()
+    }
+
     /// Try to find an element in the `RequirementDiagram` using its name.
     #[must_use]
     pub fn get_element_by_name(&self, name: &str) -> Option<&Element> {
         self.elements.get(name)
     }
+
+    /// Try to find an element in the `RequirementDiagram` using its name and
+    /// borrow it mutably so it can be updated in place.
+    pub fn get_element_by_name_mut(&mut self, name: &str) -> Option<&mut Element> {
+        self.elements.get_mut(name)
+    }
 }
 
 // implement the Display trait
 impl fmt::Display for RequirementDiagram {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // initialize the erDiagram
-        let mut out_str = "requirementDiagram".to_string();
+        write!(f, "{}", self.render_with(&RenderOptions::default()))
+    }
+}
+
+impl RequirementDiagram {
+    /// Render the `RequirementDiagram` to mermaid syntax using the options provided.
+    #[must_use]
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        // initialize the requirementDiagram
+        let mut out_str = options.keyword_or(Self::DIAGRAM_KEYWORD).to_string();
+        out_str = utils::append_accessibility(
+            out_str,
+            self.acc_title.as_deref(),
+            self.acc_descr.as_deref(),
+        );
+
+        // append annotations as comments if the diagram has them
+        if !self.annotations.is_empty() {
+            let annotations = self.annotations.iter().map(|text| {
+                text.lines()
+                    .map(|line| format!("%% {line}"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            });
+            out_str = utils::append_items_with(out_str, annotations, "Annotations", 4, options);
+        }
 
         // append elements if the diagram has them
         if !self.elements.is_empty() {
-            out_str = utils::append_items(out_str, self.elements.values(), "Elements", 4);
+            // sort the elements by name so the output is deterministic
+            let mut elements: Vec<&Element> = self.elements.values().collect();
+            elements.sort_by(|a, b| a.name.cmp(&b.name));
+            let elements = number_items(elements, options);
+            out_str = utils::append_items_with(out_str, elements, "Elements", 4, options);
         }
 
         // append requirements if the diagram has them
         if !self.requirements.is_empty() {
-            out_str = utils::append_items(out_str, self.requirements.values(), "Requirements", 4);
+            let requirements = number_items(self.requirement_blocks(options), options);
+            out_str = utils::append_items_with(out_str, requirements, "Requirements", 4, options);
         }
 
         // append relationships if the diagram has them
         if !self.relationships.is_empty() {
-            out_str = utils::append_items(out_str, &self.relationships, "Relationships", 4);
+            let relationships = number_items(&self.relationships, options);
+            out_str = utils::append_items_with(out_str, relationships, "Relationships", 4, options);
+        }
+
+        // append risk styling (if it's enabled) and the style of each styled requirement
+        let styles = self.styles();
+        if !styles.is_empty() {
+            out_str = utils::append_items_with(out_str, styles, "Styling", 4, options);
+        }
+        options.line_ending.apply(&out_str)
+    }
+
+    // Format the requirements sorted by name, optionally grouping them by type
+    fn requirement_blocks(&self, options: &RenderOptions) -> Vec<String> {
+        // sort the requirements by name so the output is deterministic
+        let mut requirements: Vec<&Requirement> = self.requirements.values().collect();
+        requirements.sort_by(|a, b| a.name.cmp(&b.name));
+        if !options.group_requirements {
+            return requirements
+                .into_iter()
+                .map(|req| req.render(options))
+                .collect();
+        }
+        // sort by type, keeping the name order within each group
+        requirements.sort_by_cached_key(|req| req.kind.to_string());
+        let mut blocks: Vec<String> = Vec::new();
+        let mut group_kind: Option<&RequirementType> = None;
+        for req in requirements {
+            if group_kind != Some(&req.kind) && !options.compact {
+                blocks.push(format!("%% {}", req.kind));
+            }
+            group_kind = Some(&req.kind);
+            blocks.push(req.render(options));
+        }
+        blocks
+    }
+
+    // Format the risk classes (if enabled) followed by the inline styles of the requirements
+    fn styles(&self) -> Vec<String> {
+        let mut styles = if self.risk_styling {
+            self.risk_styles()
+        } else {
+            Vec::new()
+        };
+        // sort the requirements by name so the styles are deterministic
+        let mut requirements: Vec<&Requirement> = self.requirements.values().collect();
+        requirements.sort_by(|a, b| a.name.cmp(&b.name));
+        for req in requirements {
+            if let Some(css) = req.style.as_deref() {
+                styles.push(format!("style {} {css}", req.name));
+            }
+        }
+        styles
+    }
+
+    // Format the class definitions for each risk and the class of each requirement
+    fn risk_styles(&self) -> Vec<String> {
+        let mut styles: Vec<String> = [
+            (Risk::High, "#f88"),
+            (Risk::Medium, "#fd8"),
+            (Risk::Low, "#8d8"),
+        ]
+        .iter()
+        .map(|(risk, fill)| format!("classDef {} fill:{fill}", risk_class(risk)))
+        .collect();
+        // sort the requirements by name so the assignments are deterministic
+        let mut requirements: Vec<&Requirement> = self.requirements.values().collect();
+        requirements.sort_by(|a, b| a.name.cmp(&b.name));
+        for req in requirements {
+            if let Some(risk) = &req.risk {
+                styles.push(format!("class {} {}", req.name, risk_class(risk)));
+            }
         }
-        write!(f, "{out_str}")
+        styles
+    }
+
+    /// Render the `RequirementDiagram` with the default options, same as `to_string()`.
+    #[must_use]
+    pub fn to_pretty(&self) -> String {
+        self.render_with(&RenderOptions::default())
+    }
+
+    /// Render the `RequirementDiagram` without the comments around each section.
+    #[must_use]
+    pub fn to_minified(&self) -> String {
+        self.render_with(&RenderOptions::new().with_compact(true))
     }
 }
 
@@ -79,7 +303,7 @@ impl fmt::Display for RequirementDiagram {
 impl RequirementDiagram {
     /// Add a requirement to `RequirementDiagram.requirements`, keyed by the requirement's name.
     pub fn add_requirement(&mut self, req: Requirement) {
-        self.requirements.insert(req.name.to_string(), req);
+        self.requirements.insert(req.name.clone(), req);
     }
 
     /// Add a requirement to the `RequirementDiagram` on creation by chaining with [`RequirementDiagram::new()`].
@@ -88,11 +312,23 @@ impl RequirementDiagram {
         self
     }
 
+    /// The number of requirements in the `RequirementDiagram`.
+    #[must_use]
+    pub fn requirement_count(&self) -> usize {
+        self.requirements.len()
+    }
+
     /// Try to find a requirement in the `RequirementDiagram` using its name.
     #[must_use]
     pub fn get_requirement_by_name(&self, name: &str) -> Option<&Requirement> {
         self.requirements.get(name)
     }
+
+    /// Try to find a requirement in the `RequirementDiagram` using its name and
+    /// borrow it mutably so it can be updated in place.
+    pub fn get_requirement_by_name_mut(&mut self, name: &str) -> Option<&mut Requirement> {
+        self.requirements.get_mut(name)
+    }
 }
 
 // ============================================================
@@ -120,18 +356,132 @@ impl RequirementDiagram {
         self.relationships.push(relationship);
     }
 
+    /// Try to add a relationship, skipping it if the diagram already has an identical one.
+    ///
+    /// Relationships are identical if every field matches, including the note and
+    /// connector. Returns `Ok(true)` if the relationship was added and `Ok(false)`
+    /// if it was skipped as a duplicate.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::MissingElement`] if the source or target isn't found
+    /// in the list of elements or requirements, instead of panicking like
+    /// [`RequirementDiagram::add_relationship()`].
+    pub fn try_add_relationship(
+        &mut self,
+        relationship: Relationship,
+    ) -> Result<bool, MormaidError> {
+        for name in [&relationship.source, &relationship.target] {
+            if !self.found_in_diagram(name) {
+                return Err(MormaidError::MissingElement(name.clone()));
+            }
+        }
+        if self.relationships.contains(&relationship) {
+            return Ok(false);
+        }
+        self.relationships.push(relationship);
+        Ok(true)
+    }
+
+    /// Add a relationship, creating an element of `default_kind` for each
+    /// endpoint that isn't found in the diagram instead of panicking.
+    ///
+    /// Unlike the ERD, a `RequirementDiagram` doesn't create missing endpoints by
+    /// default, see [`RequirementDiagram::add_relationship()`]. An endpoint that's
+    /// an existing element or requirement is left unchanged.
+    pub fn add_relationship_creating(&mut self, relationship: Relationship, default_kind: &str) {
+        for name in [&relationship.source, &relationship.target] {
+            if !self.found_in_diagram(name) {
+                self.add_element(Element::new(name, default_kind));
+            }
+        }
+        self.relationships.push(relationship);
+    }
+
     /// Add a relationship to the `RequirementDiagram` on creation by chaining with [`RequirementDiagram::new()`].
     pub fn with_relationship(mut self, relationship: Relationship) -> Self {
         self.add_relationship(relationship);
         self
     }
 
+    /// Remove every relationship from the `RequirementDiagram` while keeping its elements and requirements.
+    pub fn clear_relationships(&mut self) {
+        self.relationships.clear();
+    }
+
+    /// List the relationships of one kind, in the order they were added.
+    #[must_use]
+    pub fn relationships_of_kind(&self, kind: &RelationshipType) -> Vec<&Relationship> {
+        self.relationships
+            .iter()
+            .filter(|r| &r.kind == kind)
+            .collect()
+    }
+
+    /// The number of relationships in the `RequirementDiagram`.
+    #[must_use]
+    pub fn relationship_count(&self) -> usize {
+        self.relationships.len()
+    }
+
     // Check if a given element or requirement exists with the name provided
     fn found_in_diagram(&self, name: &str) -> bool {
         self.elements.contains_key(name) || self.requirements.contains_key(name)
     }
 }
 
+// ============================================================
+// Implement RequirementDiagram methods to combine diagrams
+// ============================================================
+impl RequirementDiagram {
+    /// Merge the elements, requirements, relationships, and annotations of another diagram into this one.
+    ///
+    /// Elements and requirements are combined by name. If both diagrams have an
+    /// element (or requirement) with the same name, the one from `other` replaces
+    /// the existing one, just like calling [`RequirementDiagram::add_element()`] or
+    /// [`RequirementDiagram::add_requirement()`] with it. The relationships from
+    /// `other` are appended after the existing relationships, and its annotations
+    /// after the existing annotations.
+    ///
+    /// # Errors
+    /// Returns an error, and leaves this diagram unchanged, if:
+    /// - a relationship from `other` references an element or requirement that's in
+    ///   neither diagram ([`MormaidError::MissingElement`])
+    /// - a name is an element in one diagram and a requirement in the other, which
+    ///   would make the relationships to it ambiguous ([`MormaidError::AmbiguousName`])
+    pub fn merge(&mut self, other: RequirementDiagram) -> Result<(), MormaidError> {
+        // check everything before changing this diagram so a failed merge changes nothing
+        let ambiguous = other
+            .elements
+            .keys()
+            .find(|name| self.requirements.contains_key(*name))
+            .or_else(|| {
+                other
+                    .requirements
+                    .keys()
+                    .find(|name| self.elements.contains_key(*name))
+            });
+        if let Some(name) = ambiguous {
+            return Err(MormaidError::AmbiguousName(name.clone()));
+        }
+        for relationship in &other.relationships {
+            for name in [&relationship.source, &relationship.target] {
+                if !self.found_in_diagram(name) && !other.found_in_diagram(name) {
+                    return Err(MormaidError::MissingElement(name.clone()));
+                }
+            }
+        }
+        for element in other.elements.into_values() {
+            self.add_element(element);
+        }
+        for req in other.requirements.into_values() {
+            self.add_requirement(req);
+        }
+        self.relationships.extend(other.relationships);
+        self.annotations.extend(other.annotations);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -224,6 +574,116 @@ mod test {
             assert_eq!(diagram.relationships.len(), 1);
         }
 
+        #[test]
+        fn count_elements_requirements_and_relationships() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID))
+                .with_requirement(Requirement::new(REQ_KIND, "other", "1.2"))
+                .with_relationship(Relationship::new(
+                    ELEMENT_NAME,
+                    REQ_NAME,
+                    RelationshipType::Satisfies,
+                ));
+            // act & assert
+            assert_eq!(diagram.element_count(), 1);
+            assert_eq!(diagram.requirement_count(), 2);
+            assert_eq!(diagram.relationship_count(), 1);
+        }
+
+        #[test]
+        fn empty_and_populated_diagrams() {
+            // arrange
+            let styled = RequirementDiagram::new().with_risk_styling();
+            let populated = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            // act & assert
+            assert!(RequirementDiagram::new().is_empty());
+            assert!(styled.is_empty());
+            assert!(!populated.is_empty());
+        }
+
+        #[test]
+        fn filter_relationships_by_kind() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_element(Element::new("test_suite", "test suite"))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID))
+                .with_relationship(Relationship::new(
+                    ELEMENT_NAME,
+                    REQ_NAME,
+                    RelationshipType::Satisfies,
+                ))
+                .with_relationship(Relationship::new(
+                    "test_suite",
+                    REQ_NAME,
+                    RelationshipType::Verifies,
+                ));
+            // act
+            let got = diagram.relationships_of_kind(&RelationshipType::Verifies);
+            // assert
+            assert_eq!(got.len(), 1);
+            assert_eq!(got[0].source, "test_suite");
+            assert!(diagram
+                .relationships_of_kind(&RelationshipType::Traces)
+                .is_empty());
+        }
+
+        #[test]
+        fn update_requirement_risk_through_mutable_lookup() {
+            // arrange
+            let mut diagram = RequirementDiagram::new().with_requirement(
+                Requirement::new(REQ_KIND, REQ_NAME, REQ_ID).with_risk(Risk::Low),
+            );
+            // act
+            if let Some(req) = diagram.get_requirement_by_name_mut(REQ_NAME) {
+                req.risk = Some(Risk::High);
+            }
+            // assert
+            let req = diagram
+                .get_requirement_by_name(REQ_NAME)
+                .expect("Expected requirement");
+            assert_eq!(req.risk, Some(Risk::High));
+            assert!(diagram.get_requirement_by_name_mut("Fake").is_none());
+        }
+
+        #[test]
+        fn update_element_through_mutable_lookup() {
+            // arrange
+            let mut diagram =
+                RequirementDiagram::new().with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND));
+            // act
+            if let Some(element) = diagram.get_element_by_name_mut(ELEMENT_NAME) {
+                element.kind = "service".to_string();
+            }
+            // assert
+            let element = diagram
+                .get_element_by_name(ELEMENT_NAME)
+                .expect("Expected element");
+            assert_eq!(element.kind, "service");
+        }
+
+        #[test]
+        fn clear_relationships_keeps_elements_and_requirements() {
+            // arrange
+            let mut diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID))
+                .with_relationship(Relationship::new(
+                    ELEMENT_NAME,
+                    REQ_NAME,
+                    RelationshipType::Satisfies,
+                ));
+            // act
+            diagram.clear_relationships();
+            // assert
+            assert!(diagram.relationships.is_empty());
+            assert_eq!(diagram.elements.len(), 1);
+            assert_eq!(diagram.requirements.len(), 1);
+        }
+
         #[test]
         #[should_panic = "Fake isn't found in the list of elements or requirements"]
         fn add_invalid_relationship_should_panic() {
@@ -236,10 +696,164 @@ mod test {
                 RelationshipType::Satisfies,
             ));
         }
+
+        #[test]
+        fn try_add_identical_relationship_twice() {
+            // arrange
+            let mut diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            let relationship =
+                || Relationship::new(ELEMENT_NAME, REQ_NAME, RelationshipType::Satisfies);
+            // act
+            let first = diagram.try_add_relationship(relationship());
+            let second = diagram.try_add_relationship(relationship());
+            // assert
+            assert_eq!(first, Ok(true));
+            assert_eq!(second, Ok(false));
+            assert_eq!(diagram.relationship_count(), 1);
+            assert_eq!(
+                diagram
+                    .to_string()
+                    .matches("foo - satisfies -> milestone")
+                    .count(),
+                1
+            );
+        }
+
+        #[test]
+        fn try_add_relationship_with_missing_element_should_error() {
+            // arrange
+            let mut diagram = RequirementDiagram::new();
+            // act
+            let got = diagram.try_add_relationship(Relationship::new(
+                "Fake",
+                REQ_NAME,
+                RelationshipType::Traces,
+            ));
+            // assert
+            assert_eq!(got, Err(MormaidError::MissingElement("Fake".to_string())));
+            assert!(diagram.relationships.is_empty());
+        }
+
+        #[test]
+        fn add_relationship_creating_missing_element() {
+            // arrange
+            let mut diagram = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            // act
+            diagram.add_relationship_creating(
+                Relationship::new("login_test", REQ_NAME, RelationshipType::Verifies),
+                "test suite",
+            );
+            // assert
+            assert_eq!(diagram.relationship_count(), 1);
+            assert_eq!(diagram.element_count(), 1);
+            let element = diagram
+                .get_element_by_name("login_test")
+                .expect("Expected the element to be created");
+            assert_eq!(element.kind, "test suite");
+            assert_eq!(diagram.requirement_count(), 1);
+        }
+    }
+
+    mod merge_tests {
+
+        use super::*;
+
+        #[test]
+        fn merge_diagrams_without_collisions() {
+            // arrange
+            let mut diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID))
+                .with_relationship(Relationship::new(
+                    ELEMENT_NAME,
+                    REQ_NAME,
+                    RelationshipType::Satisfies,
+                ));
+            let other = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, "speed", "1.2"))
+                .with_relationship(Relationship::new(
+                    "speed",
+                    "speed",
+                    RelationshipType::Refines,
+                ));
+            // act
+            diagram
+                .merge(other)
+                .expect("Expected the diagrams to merge");
+            // assert
+            assert_eq!(diagram.elements.len(), 1);
+            assert_eq!(diagram.requirements.len(), 2);
+            assert_eq!(diagram.relationships.len(), 2);
+        }
+
+        #[test]
+        fn merge_replaces_requirement_with_same_name() {
+            // arrange
+            let mut diagram = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            let other = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, "2.0").with_risk(Risk::High))
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_relationship(Relationship::new(
+                    ELEMENT_NAME,
+                    REQ_NAME,
+                    RelationshipType::Verifies,
+                ));
+            // act
+            diagram
+                .merge(other)
+                .expect("Expected the diagrams to merge");
+            // assert
+            let requirement = diagram
+                .get_requirement_by_name(REQ_NAME)
+                .expect("Expected requirement but got None");
+            assert_eq!(diagram.requirements.len(), 1);
+            assert_eq!(requirement.id, "2.0");
+            assert_eq!(requirement.risk, Some(Risk::High));
+            assert_eq!(diagram.relationships.len(), 1);
+        }
+
+        #[test]
+        fn merge_with_invalid_relationship_should_error_without_changes() {
+            // arrange
+            let mut diagram = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            let mut other =
+                RequirementDiagram::new().with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND));
+            other.relationships.push(Relationship::new(
+                "Fake",
+                REQ_NAME,
+                RelationshipType::Traces,
+            ));
+            // act
+            let got = diagram.merge(other);
+            // assert
+            assert_eq!(got, Err(MormaidError::MissingElement("Fake".to_string())));
+            assert_eq!(diagram.element_count(), 0);
+            assert_eq!(diagram.relationship_count(), 0);
+        }
+
+        #[test]
+        fn merge_element_with_requirement_name_should_error() {
+            // arrange
+            let mut diagram = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            let other =
+                RequirementDiagram::new().with_element(Element::new(REQ_NAME, ELEMENT_KIND));
+            // act
+            let got = diagram.merge(other);
+            // assert
+            assert_eq!(got, Err(MormaidError::AmbiguousName(REQ_NAME.to_string())));
+            assert_eq!(diagram.element_count(), 0);
+        }
     }
 
     mod display_tests {
         use super::*;
+        use crate::render::LineEnding;
 
         #[test]
         fn display_empty_diagram() {
@@ -251,6 +865,98 @@ mod test {
             assert_eq!(got, wanted);
         }
 
+        #[test]
+        fn display_requirements_with_sequential_numbers() {
+            // arrange
+            let wanted = concat!(
+                "requirementDiagram\n",
+                "    %% Annotations start\n",
+                "    %% reviewed\n",
+                "    %% Annotations end\n",
+                "    %% Requirements start\n",
+                "    %% functionalRequirement\n",
+                "    %% [1]\n",
+                "    functionalRequirement b {\n",
+                "        id: 2\n",
+                "    }\n",
+                "    %% requirement\n",
+                "    %% [2]\n",
+                "    requirement a {\n",
+                "        id: 1\n",
+                "        risk: High\n",
+                "    }\n",
+                "    %% [3]\n",
+                "    requirement c {\n",
+                "        id: 3\n",
+                "    }\n",
+                "    %% Requirements end\n",
+                "    %% Relationships start\n",
+                "    %% [1]\n",
+                "    a - contains -> b\n",
+                "    %% Relationships end\n",
+                "    %% Styling start\n",
+                "    classDef highRisk fill:#f88\n",
+                "    classDef mediumRisk fill:#fd8\n",
+                "    classDef lowRisk fill:#8d8\n",
+                "    class a highRisk\n",
+                "    %% Styling end",
+            );
+            let diagram = RequirementDiagram::new()
+                .with_annotation("reviewed")
+                .with_requirement(Requirement::new(REQ_KIND, "a", "1").with_risk(Risk::High))
+                .with_requirement(Requirement::new(RequirementType::Functional, "b", "2"))
+                .with_requirement(Requirement::new(REQ_KIND, "c", "3"))
+                .with_relationship(Relationship::new("a", "b", RelationshipType::Contains))
+                .with_risk_styling();
+            let options = RenderOptions::new()
+                .with_number_items(true)
+                .with_group_requirements(true);
+            // act
+            let got = diagram.render_with(&options);
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn display_diagram_with_risk_styling() {
+            // arrange
+            let wanted = concat!(
+                "    classDef highRisk fill:#f88\n",
+                "    classDef mediumRisk fill:#fd8\n",
+                "    classDef lowRisk fill:#8d8\n",
+                "    class a highRisk\n",
+                "    class b mediumRisk\n",
+                "    class c lowRisk",
+            );
+            let diagram = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, "a", "1").with_risk(Risk::High))
+                .with_requirement(Requirement::new(REQ_KIND, "b", "2").with_risk(Risk::Medium))
+                .with_requirement(Requirement::new(REQ_KIND, "c", "3").with_risk(Risk::Low))
+                .with_requirement(Requirement::new(REQ_KIND, "d", "4"))
+                .with_risk_styling();
+            // act
+            let got = diagram.to_minified();
+            // assert
+            assert!(
+                got.ends_with(wanted),
+                "styling not found at the end of:\n{got}"
+            );
+            assert!(!got.contains("class d "));
+        }
+
+        #[test]
+        fn display_diagram_without_risk_styling_has_no_classes() {
+            // arrange
+            let diagram = RequirementDiagram::new().with_requirement(
+                Requirement::new(REQ_KIND, REQ_NAME, REQ_ID).with_risk(Risk::High),
+            );
+            // act
+            let got = diagram.to_string();
+            // assert
+            assert!(!got.contains("classDef"));
+            assert!(!got.contains("class "));
+        }
+
         #[test]
         fn display_diagram_with_element_and_requirement() {
             // arrange
@@ -274,7 +980,223 @@ mod test {
                 .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID).with_risk(Risk::Low))
                 .to_string();
             // assert
-            assert_eq!(got, wanted, "\n\nGot:\n{got}\n\nWanted:\n{wanted}");
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn display_relationship_note_after_relationship() {
+            // arrange
+            let wanted = concat!(
+                "requirementDiagram\n",
+                "    %% Elements start\n",
+                "    element foo {\n",
+                "        type: \"brief\"\n",
+                "    }\n",
+                "    %% Elements end\n",
+                "    %% Requirements start\n",
+                "    requirement milestone {\n",
+                "        id: 1.1.1\n",
+                "    }\n",
+                "    %% Requirements end\n",
+                "    %% Relationships start\n",
+                "    foo - satisfies -> milestone\n",
+                "    %% foo was built to meet the milestone\n",
+                "    %% Relationships end",
+            );
+            // act
+            let got = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID))
+                .with_relationship(
+                    Relationship::new(ELEMENT_NAME, REQ_NAME, RelationshipType::Satisfies)
+                        .with_note("foo was built to meet the milestone"),
+                )
+                .to_string();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn render_compact_diagram_with_all_components() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID).with_risk(Risk::Low))
+                .with_relationship(Relationship::new(
+                    ELEMENT_NAME,
+                    REQ_NAME,
+                    RelationshipType::Copies,
+                ));
+            let wanted = concat!(
+                "requirementDiagram\n",
+                "    element foo {\n",
+                "        type: \"brief\"\n",
+                "    }\n",
+                "    requirement milestone {\n",
+                "        id: 1.1.1\n",
+                "        risk: Low\n",
+                "    }\n",
+                "    foo - copies -> milestone",
+            );
+            // act
+            let got = diagram.render_with(&RenderOptions::new().with_compact(true));
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+            // the default output only differs by the section comments
+            let default = diagram.to_string();
+            let default_without_comments: Vec<&str> = default
+                .lines()
+                .filter(|line| !line.trim_start().starts_with("%%"))
+                .collect();
+            assert_eq!(default_without_comments.join("\n"), got);
+        }
+
+        #[test]
+        fn render_pretty_and_minified() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            // act
+            let pretty = diagram.to_pretty();
+            let minified = diagram.to_minified();
+            // assert
+            assert_eq!(pretty, diagram.to_string());
+            assert!(pretty.contains("%% Elements start"));
+            assert!(!minified.contains("%%"));
+        }
+
+        #[test]
+        fn render_style_lines_for_styled_requirements() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_requirement(
+                    Requirement::new(REQ_KIND, REQ_NAME, REQ_ID)
+                        .with_style("fill:#f9f,stroke:#333"),
+                )
+                .with_requirement(Requirement::new(REQ_KIND, "other", "1.2"));
+            let wanted = concat!(
+                "requirementDiagram\n",
+                "    %% Requirements start\n",
+                "    requirement milestone {\n",
+                "        id: 1.1.1\n",
+                "    }\n",
+                "    requirement other {\n",
+                "        id: 1.2\n",
+                "    }\n",
+                "    %% Requirements end\n",
+                "    %% Styling start\n",
+                "    style milestone fill:#f9f,stroke:#333\n",
+                "    %% Styling end",
+            );
+            // act
+            let got = diagram.to_string();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+            let unstyled = RequirementDiagram::new()
+                .with_requirement(Requirement::new(REQ_KIND, "other", "1.2"))
+                .to_string();
+            assert!(!unstyled.contains("Styling"));
+        }
+
+        #[test]
+        fn render_accessibility_directives_after_keyword() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_annotation("Login flow")
+                .with_acc_title("Login requirements")
+                .with_acc_descr("What the login flow must do");
+            let wanted = concat!(
+                "requirementDiagram\n",
+                "    accTitle: Login requirements\n",
+                "    accDescr: What the login flow must do\n",
+                "    %% Annotations start\n",
+                "    %% Login flow\n",
+                "    %% Annotations end",
+            );
+            // act
+            let got = diagram.to_string();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+            assert_eq!(RequirementDiagram::new().to_string(), "requirementDiagram");
+        }
+
+        #[test]
+        fn render_annotations_as_comments() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_annotation("Requirements for the login flow")
+                .with_annotation("Owned by the auth team\nReviewed quarterly")
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            let wanted = concat!(
+                "requirementDiagram\n",
+                "    %% Annotations start\n",
+                "    %% Requirements for the login flow\n",
+                "    %% Owned by the auth team\n",
+                "    %% Reviewed quarterly\n",
+                "    %% Annotations end\n",
+                "    %% Requirements start\n",
+                "    requirement milestone {\n",
+                "        id: 1.1.1\n",
+                "    }\n",
+                "    %% Requirements end",
+            );
+            // act
+            let got = diagram.to_string();
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+            assert_eq!(diagram.requirement_count(), 1);
+            assert!(diagram
+                .get_requirement_by_name("Requirements for the login flow")
+                .is_none());
+        }
+
+        #[test]
+        fn render_requirements_grouped_by_type() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_requirement(Requirement::new(
+                    RequirementType::Performance,
+                    "latency",
+                    "2",
+                ))
+                .with_requirement(Requirement::new(RequirementType::Functional, "login", "1"))
+                .with_requirement(Requirement::new(RequirementType::Functional, "export", "3"));
+            let wanted = concat!(
+                "requirementDiagram\n",
+                "    %% Requirements start\n",
+                "    %% functionalRequirement\n",
+                "    functionalRequirement export {\n",
+                "        id: 3\n",
+                "    }\n",
+                "    functionalRequirement login {\n",
+                "        id: 1\n",
+                "    }\n",
+                "    %% performanceRequirement\n",
+                "    performanceRequirement latency {\n",
+                "        id: 2\n",
+                "    }\n",
+                "    %% Requirements end",
+            );
+            let options = RenderOptions::new().with_group_requirements(true);
+            // act
+            let got = diagram.render_with(&options);
+            // assert
+            crate::assert_mermaid_eq!(got, wanted);
+        }
+
+        #[test]
+        fn render_with_crlf_line_endings() {
+            // arrange
+            let diagram = RequirementDiagram::new()
+                .with_element(Element::new(ELEMENT_NAME, ELEMENT_KIND))
+                .with_requirement(Requirement::new(REQ_KIND, REQ_NAME, REQ_ID));
+            let options = RenderOptions::new().with_line_ending(LineEnding::Crlf);
+            // act
+            let got = diagram.render_with(&options);
+            // assert
+            assert!(got.contains("requirementDiagram\r\n"));
+            assert_eq!(got.matches("\r\n").count(), got.matches('\n').count());
         }
 
         #[test]
@@ -308,7 +1230,7 @@ mod test {
                 ))
                 .to_string();
             // assert
-            assert_eq!(got, wanted, "\n\nGot:\n{got}\n\nWanted:\n{wanted}");
+            crate::assert_mermaid_eq!(got, wanted);
         }
     }
 }
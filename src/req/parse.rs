@@ -0,0 +1,438 @@
+// ==================================================================
+// Parse a RequirementDiagram from mermaid text
+// ==================================================================
+
+use super::{
+    Connector, Element, Relationship, RelationshipType, Requirement, RequirementDiagram,
+    RequirementType, Risk, VerifyMethod,
+};
+use crate::{utils, ParseError};
+
+// A `key: value` line inside an element or requirement block with its line number
+struct Field<'a> {
+    line: usize,
+    key: &'a str,
+    value: &'a str,
+}
+
+impl RequirementDiagram {
+    /// Parse a `RequirementDiagram` from mermaid `requirementDiagram` syntax.
+    ///
+    /// Element blocks, requirement blocks, and relationship lines are read back
+    /// into the diagram. The `%%` comments on the lines after a relationship become
+    /// its note, joined with line breaks, except for the `%% ... start`, `%% ... end`, and `%% [n]` markers
+    /// added when rendering, which are skipped along with other comments. Each
+    /// comment between the `%% Annotations start` and `end` markers becomes an
+    /// annotation, so a multi-line annotation is read back as one per line, and
+    /// annotations are skipped in compact output, which has no markers. The
+    /// `classDef` and `class` lines added by risk styling turn risk styling on.
+    /// Requirement text is decoded from the default [`crate::render::EscapeStyle`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use crate::mormaid::req::RequirementDiagram;
+    ///
+    /// let input = "requirementDiagram\n    requirement test_req {\n        id: 1\n    }";
+    /// let diagram = RequirementDiagram::from_mermaid(input).unwrap();
+    /// assert_eq!(diagram.requirement_count(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] with the line number of the first line that
+    /// isn't valid, e.g. an unknown requirement type or a requirement without an
+    /// id, or of the first relationship that references a missing element or requirement.
+    pub fn from_mermaid(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+        let mut diagram = RequirementDiagram::new();
+        let mut keyword_found = false;
+        // the relationships are checked once every element and requirement is known
+        let mut relationship_lines = Vec::new();
+        let mut last_was_relationship = false;
+        // the styles are applied once every requirement is known
+        let mut style_lines = Vec::new();
+
+        while let Some((line_no, line)) = lines.next() {
+            // treat the comments after a relationship as the lines of its note
+            if let Some(comment) = line.strip_prefix("%%") {
+                let comment = comment.trim();
+                if comment == "Annotations start" {
+                    diagram.annotations.extend(read_annotations(&mut lines));
+                    last_was_relationship = false;
+                    continue;
+                }
+                if last_was_relationship && !is_render_marker(comment) {
+                    if let Some(rel) = diagram.relationships.last_mut() {
+                        rel.note = Some(match rel.note.take() {
+                            Some(note) => format!("{note}\n{comment}"),
+                            None => comment.to_string(),
+                        });
+                    }
+                    continue;
+                }
+                last_was_relationship = false;
+                continue;
+            }
+            last_was_relationship = false;
+            // the first line must start the diagram
+            if !keyword_found {
+                if line != RequirementDiagram::DIAGRAM_KEYWORD {
+                    let message = format!("expected {}", RequirementDiagram::DIAGRAM_KEYWORD);
+                    return Err(ParseError::new(line_no, &message));
+                }
+                keyword_found = true;
+                continue;
+            }
+            if let Some(title) = line.strip_prefix("accTitle:") {
+                diagram.acc_title = Some(title.trim().to_string());
+            } else if let Some(descr) = line.strip_prefix("accDescr:") {
+                diagram.acc_descr = Some(descr.trim().to_string());
+            } else if line.strip_suffix('{').map(str::trim) == Some("accDescr") {
+                diagram.acc_descr = Some(read_block(&mut lines, line_no)?);
+            } else if let Some(header) = line.strip_suffix('{') {
+                let Some((keyword, name)) = header.trim().split_once(char::is_whitespace) else {
+                    return Err(ParseError::new(line_no, "expected a type and a name"));
+                };
+                let fields = read_fields(&mut lines, line_no)?;
+                if keyword == "element" {
+                    diagram.add_element(parse_element(name.trim(), &fields)?);
+                } else {
+                    let kind = RequirementType::try_from(keyword)
+                        .map_err(|err| ParseError::new(line_no, &err.to_string()))?;
+                    diagram.add_requirement(parse_requirement(
+                        kind,
+                        name.trim(),
+                        &fields,
+                        line_no,
+                    )?);
+                }
+            } else if line.starts_with("classDef ") || line.starts_with("class ") {
+                diagram.risk_styling = true;
+            } else if let Some(style) = line.strip_prefix("style ") {
+                style_lines.push((line_no, style));
+            } else {
+                diagram
+                    .relationships
+                    .push(parse_relationship(line, line_no)?);
+                relationship_lines.push(line_no);
+                last_was_relationship = true;
+            }
+        }
+        if !keyword_found {
+            let message = format!("expected {}", RequirementDiagram::DIAGRAM_KEYWORD);
+            return Err(ParseError::new(1, &message));
+        }
+        // apply each style to the requirement it names
+        for (line_no, style) in style_lines {
+            let (name, css) = style
+                .trim()
+                .split_once(char::is_whitespace)
+                .unwrap_or((style, ""));
+            let Some(req) = diagram.get_requirement_by_name_mut(name) else {
+                let message = format!("{name} isn't found in the list of requirements");
+                return Err(ParseError::new(line_no, &message));
+            };
+            req.style = Some(css.trim().to_string());
+        }
+        // check that every relationship references something in the diagram
+        for (rel, line_no) in diagram.relationships.iter().zip(relationship_lines) {
+            for name in [&rel.source, &rel.target] {
+                if !diagram.found_in_diagram(name) {
+                    let message =
+                        format!("{name} isn't found in the list of elements or requirements");
+                    return Err(ParseError::new(line_no, &message));
+                }
+            }
+        }
+        Ok(diagram)
+    }
+}
+
+// Check if a comment was added by the renderer rather than written by a user
+fn is_render_marker(comment: &str) -> bool {
+    let is_number = comment
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .is_some_and(|n| n.parse::<usize>().is_ok());
+    is_number || comment.ends_with(" start") || comment.ends_with(" end")
+}
+
+// Read the comments up to the `%% Annotations end` marker, without their `%% ` prefix
+fn read_annotations<'a>(lines: &mut impl Iterator<Item = (usize, &'a str)>) -> Vec<String> {
+    let mut annotations = Vec::new();
+    for (_, line) in lines.by_ref() {
+        let Some(comment) = line.strip_prefix("%%") else {
+            continue;
+        };
+        let comment = comment.strip_prefix(' ').unwrap_or(comment);
+        if comment == "Annotations end" {
+            break;
+        }
+        annotations.push(comment.to_string());
+    }
+    annotations
+}
+
+// Read the lines of a multi-line block, e.g. `accDescr { ... }`, up to its closing bracket
+fn read_block<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    start: usize,
+) -> Result<String, ParseError> {
+    let mut block = Vec::new();
+    for (_, line) in lines.by_ref() {
+        if line == "}" {
+            return Ok(block.join("\n"));
+        }
+        block.push(line);
+    }
+    Err(ParseError::new(start, "expected a closing bracket"))
+}
+
+// Read the `key: value` lines of a block up to and including its closing bracket
+fn read_fields<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    start: usize,
+) -> Result<Vec<Field<'a>>, ParseError> {
+    let mut fields = Vec::new();
+    for (line_no, line) in lines.by_ref() {
+        if line == "}" {
+            return Ok(fields);
+        }
+        if line.starts_with("%%") {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(ParseError::new(
+                line_no,
+                "expected a field like `key: value`",
+            ));
+        };
+        fields.push(Field {
+            line: line_no,
+            key: key.trim(),
+            value: unquote(value.trim()),
+        });
+    }
+    Err(ParseError::new(start, "expected a closing bracket"))
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn parse_element(name: &str, fields: &[Field]) -> Result<Element, ParseError> {
+    let mut element = Element::new(name, "");
+    for field in fields {
+        match field.key {
+            "type" => element.kind = field.value.to_string(),
+            "docref" => element.docref = Some(field.value.to_string()),
+            key => return Err(unknown_field(field.line, key)),
+        }
+    }
+    Ok(element)
+}
+
+fn parse_requirement(
+    kind: RequirementType,
+    name: &str,
+    fields: &[Field],
+    start: usize,
+) -> Result<Requirement, ParseError> {
+    let Some(id) = fields.iter().find(|field| field.key == "id") else {
+        return Err(ParseError::new(
+            start,
+            &format!("{name} doesn't have an id"),
+        ));
+    };
+    let mut req = Requirement::new(kind, name, id.value);
+    for field in fields {
+        let to_parse_error =
+            |err: crate::MormaidError| ParseError::new(field.line, &err.to_string());
+        match field.key {
+            "id" => (),
+            "text" => req.text = Some(utils::unescape_quoted(field.value)),
+            "risk" => req.risk = Some(Risk::try_from(field.value).map_err(to_parse_error)?),
+            "verifymethod" => {
+                let method = VerifyMethod::try_from(field.value).map_err(to_parse_error)?;
+                req.verify_method = Some(method);
+            }
+            key => return Err(unknown_field(field.line, key)),
+        }
+    }
+    Ok(req)
+}
+
+fn unknown_field(line: usize, key: &str) -> ParseError {
+    ParseError::new(line, &format!("{key} isn't a valid field"))
+}
+
+// Parse a relationship line like `source - kind -> target` or `target <- kind - source`
+fn parse_relationship(line: &str, line_no: usize) -> Result<Relationship, ParseError> {
+    let reverse = line.split_once(" <- ").and_then(|(target, rest)| {
+        rest.split_once(" - ")
+            .map(|(kind, source)| (source, kind, target, Connector::Reverse))
+    });
+    let parts = reverse.or_else(|| {
+        line.split_once(" - ").and_then(|(source, rest)| {
+            rest.split_once(" -> ")
+                .map(|(kind, target)| (source, kind, target, Connector::Arrow))
+        })
+    });
+    let Some((source, kind, target, connector)) = parts else {
+        return Err(ParseError::new(
+            line_no,
+            "expected a relationship like `source - kind -> target`",
+        ));
+    };
+    let kind = RelationshipType::try_from(kind.trim())
+        .map_err(|err| ParseError::new(line_no, &err.to_string()))?;
+    Ok(Relationship::new(source.trim(), target.trim(), kind).with_connector(connector))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RequirementDiagram {
+        RequirementDiagram::new()
+            .with_acc_title("Test requirements")
+            .with_acc_descr("Requirements for the test entity\nand how they're covered")
+            .with_annotation("Reviewed by the QA team")
+            .with_element(Element::new("test_entity", "simulation").with_docref("reqs/test.md"))
+            .with_requirement(
+                Requirement::new(RequirementType::Functional, "test_req", "1.1")
+                    .with_text("the \"test\" text #1.")
+                    .with_risk(Risk::High)
+                    .with_verify_method(VerifyMethod::Test)
+                    .with_style("fill:#f9f,stroke:#333"),
+            )
+            .with_requirement(Requirement::new(
+                RequirementType::Default,
+                "other_req",
+                "1.2",
+            ))
+            .with_relationship(
+                Relationship::new("test_entity", "test_req", RelationshipType::Satisfies)
+                    .with_note("covered by the simulation"),
+            )
+            .with_relationship(Relationship::new(
+                "test_req",
+                "other_req",
+                RelationshipType::Contains,
+            ))
+            .with_relationship(
+                Relationship::new("test_entity", "other_req", RelationshipType::Traces)
+                    .with_connector(Connector::Reverse),
+            )
+    }
+
+    #[test]
+    fn round_trip_multi_line_note() {
+        // arrange
+        let wanted = RequirementDiagram::new()
+            .with_requirement(Requirement::new(RequirementType::Default, "a", "1"))
+            .with_requirement(Requirement::new(RequirementType::Default, "b", "2"))
+            .with_relationship(
+                Relationship::new("a", "b", RelationshipType::Contains)
+                    .with_note("first line\nsecond line"),
+            )
+            .with_relationship(Relationship::new("b", "a", RelationshipType::Traces));
+        // act
+        let got = RequirementDiagram::from_mermaid(&wanted.to_string())
+            .expect("Expected the rendered diagram to parse");
+        // assert
+        assert_eq!(
+            got.relationships[0].note.as_deref(),
+            Some("first line\nsecond line")
+        );
+        assert_eq!(got.relationships[1].note, None);
+        crate::assert_mermaid_eq!(got.to_string(), wanted.to_string());
+    }
+
+    #[test]
+    fn parse_reverse_relationship() {
+        // arrange
+        let input = concat!(
+            "requirementDiagram\n",
+            "requirement a {\n    id: 1\n}\n",
+            "requirement b {\n    id: 2\n}\n",
+            "b <- traces - a",
+        );
+        // act
+        let diagram = RequirementDiagram::from_mermaid(input).expect("Expected a valid diagram");
+        // assert
+        let rel = &diagram.relationships[0];
+        assert_eq!((rel.source.as_str(), rel.target.as_str()), ("a", "b"));
+        assert_eq!(rel.connector, Connector::Reverse);
+        assert_eq!(diagram.to_string().matches("b <- traces - a").count(), 1);
+    }
+
+    #[test]
+    fn round_trip_display_output() {
+        // arrange
+        let wanted = sample().to_string();
+        // act
+        let got = RequirementDiagram::from_mermaid(&wanted)
+            .expect("Expected the rendered diagram to parse")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn round_trip_minified_and_numbered_output() {
+        // arrange
+        let options = crate::render::RenderOptions::new()
+            .with_compact(true)
+            .with_number_items(true);
+        let input = sample().render_with(&options);
+        // the annotations can't be told apart from other comments without their markers
+        let mut wanted = sample();
+        wanted.annotations.clear();
+        // act
+        let got = RequirementDiagram::from_mermaid(&input).expect("Expected a valid diagram");
+        // assert
+        crate::assert_mermaid_eq!(got.to_string(), wanted.to_string());
+    }
+
+    #[test]
+    fn missing_keyword_should_error() {
+        // act
+        let got = RequirementDiagram::from_mermaid("erDiagram").map(|d| d.element_count());
+        // assert
+        assert_eq!(got, Err(ParseError::new(1, "expected requirementDiagram")));
+    }
+
+    #[test]
+    fn unknown_risk_should_error() {
+        // arrange
+        let input = "requirementDiagram\nrequirement a {\n    id: 1\n    risk: Severe\n}";
+        // act
+        let got = RequirementDiagram::from_mermaid(input).map(|d| d.requirement_count());
+        // assert
+        assert_eq!(got, Err(ParseError::new(4, "Severe isn't a valid risk")));
+    }
+
+    #[test]
+    fn relationship_to_missing_node_should_error() {
+        // arrange
+        let input = "requirementDiagram\nrequirement a {\n    id: 1\n}\na - traces -> b";
+        // act
+        let got = RequirementDiagram::from_mermaid(input).map(|d| d.relationship_count());
+        // assert
+        assert_eq!(
+            got,
+            Err(ParseError::new(
+                5,
+                "b isn't found in the list of elements or requirements"
+            ))
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use std::fmt;
 
+use crate::MormaidError;
+
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum RelationshipType {
     Contains,
     Copies,
@@ -27,11 +30,88 @@ impl fmt::Display for RelationshipType {
     }
 }
 
+/// Parse a relationship type from its mermaid keyword, e.g. `satisfies`.
+impl TryFrom<&str> for RelationshipType {
+    type Error = MormaidError;
+
+    fn try_from(keyword: &str) -> Result<Self, Self::Error> {
+        match keyword {
+            "contains" => Ok(RelationshipType::Contains),
+            "copies" => Ok(RelationshipType::Copies),
+            "derives" => Ok(RelationshipType::Derives),
+            "satisfies" => Ok(RelationshipType::Satisfies),
+            "verifies" => Ok(RelationshipType::Verifies),
+            "refines" => Ok(RelationshipType::Refines),
+            "traces" => Ok(RelationshipType::Traces),
+            _ => Err(MormaidError::UnknownVariant {
+                kind: "relationship type",
+                value: keyword.to_string(),
+            }),
+        }
+    }
+}
+
+impl RelationshipType {
+    /// Every known variant, in declaration order.
+    #[must_use]
+    pub fn all_variants() -> &'static [Self] {
+        &[
+            RelationshipType::Contains,
+            RelationshipType::Copies,
+            RelationshipType::Derives,
+            RelationshipType::Satisfies,
+            RelationshipType::Verifies,
+            RelationshipType::Refines,
+            RelationshipType::Traces,
+        ]
+    }
+
+    /// A human-readable label for the relationship read from target to source.
+    ///
+    /// Mermaid has no inverse keywords, so the label is never rendered in a
+    /// diagram. It's meant for reverse-traceability reports, e.g. a test that
+    /// `verifies` a requirement means the requirement is `"verified by"` the test.
+    #[must_use]
+    pub fn inverse_label(&self) -> &'static str {
+        match self {
+            RelationshipType::Contains => "contained by",
+            RelationshipType::Copies => "copied by",
+            RelationshipType::Derives => "derived by",
+            RelationshipType::Satisfies => "satisfied by",
+            RelationshipType::Verifies => "verified by",
+            RelationshipType::Refines => "refined by",
+            RelationshipType::Traces => "traced by",
+        }
+    }
+}
+
+/// The direction a relationship is written in, using the two forms in mermaid's grammar.
+///
+/// Both forms have the same meaning, and both are read back by
+/// [`super::RequirementDiagram::from_mermaid()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Connector {
+    /// `source - kind -> target`
+    #[default]
+    Arrow,
+    /// `target <- kind - source`
+    Reverse,
+}
+
 #[must_use]
+#[derive(PartialEq)]
 pub struct Relationship {
     pub source: String,
     pub target: String,
     pub kind: RelationshipType,
+    /// A note explaining why the relationship exists.
+    ///
+    /// Mermaid only supports comments on their own line, so each line of the note
+    /// is rendered as a `%%` comment after the relationship. It's preserved in the
+    /// diagram source but ignored when the diagram is rendered.
+    pub note: Option<String>,
+    /// The direction the relationship is written in, see [`Relationship::with_connector()`].
+    pub connector: Connector,
 }
 
 impl Relationship {
@@ -41,13 +121,72 @@ impl Relationship {
             source: source.to_string(),
             target: target.to_string(),
             kind,
+            note: None,
+            connector: Connector::default(),
         }
     }
+
+    /// Create a new relationship, rejecting one whose source is also its target.
+    ///
+    /// A requirement that satisfies (or verifies, etc.) itself is almost always a
+    /// mistake. Use [`Relationship::new()`] to allow it anyway.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::SelfRelationship`] if the source and target are the same.
+    pub fn try_new(
+        source: &str,
+        target: &str,
+        kind: RelationshipType,
+    ) -> Result<Self, MormaidError> {
+        if source == target {
+            return Err(MormaidError::SelfRelationship(source.to_string()));
+        }
+        Ok(Relationship::new(source, target, kind))
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+
+    /// Chain with `Relationship::new()` to render the relationship with a
+    /// different [`Connector`] than the default `source - kind -> target`.
+    pub fn with_connector(mut self, connector: Connector) -> Self {
+        self.connector = connector;
+        self
+    }
+
+    /// Describe the relationship from source to target, e.g. "test satisfies req".
+    #[must_use]
+    pub fn describe(&self) -> String {
+        format!("{} {} {}", self.source, self.kind, self.target)
+    }
+
+    /// Describe the relationship from target to source with the type's
+    /// [`RelationshipType::inverse_label()`], e.g. "req is satisfied by test".
+    #[must_use]
+    pub fn describe_reverse(&self) -> String {
+        format!(
+            "{} is {} {}",
+            self.target,
+            self.kind.inverse_label(),
+            self.source
+        )
+    }
 }
 
 impl fmt::Display for Relationship {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let out_str = format!("{} - {} -> {}", self.source, self.kind, self.target,);
+        let mut out_str = match self.connector {
+            Connector::Arrow => format!("{} - {} -> {}", self.source, self.kind, self.target),
+            Connector::Reverse => format!("{} <- {} - {}", self.target, self.kind, self.source),
+        };
+        // format the note (if populated) as comments on the next lines
+        if let Some(note) = self.note.as_deref() {
+            for line in note.lines() {
+                out_str += &format!("\n%% {line}");
+            }
+        }
         write!(f, "{out_str}")
     }
 }
@@ -70,6 +209,73 @@ mod tests {
         assert_eq!(got.kind, KIND);
     }
 
+    #[test]
+    fn inverse_label_of_each_type() {
+        // arrange
+        let types = [
+            (RelationshipType::Contains, "contained by"),
+            (RelationshipType::Copies, "copied by"),
+            (RelationshipType::Derives, "derived by"),
+            (RelationshipType::Satisfies, "satisfied by"),
+            (RelationshipType::Verifies, "verified by"),
+            (RelationshipType::Refines, "refined by"),
+            (RelationshipType::Traces, "traced by"),
+        ];
+        for (kind, wanted) in types {
+            // act
+            let got = kind.inverse_label();
+            // assert
+            assert_eq!(got, wanted);
+        }
+    }
+
+    #[test]
+    fn display_each_connector() {
+        // arrange
+        let connectors = [
+            (Connector::Arrow, "test - verifies -> req"),
+            (Connector::Reverse, "req <- verifies - test"),
+        ];
+        for (connector, wanted) in connectors {
+            // act
+            let got = Relationship::new("test", "req", RelationshipType::Verifies)
+                .with_connector(connector)
+                .to_string();
+            // assert
+            assert_eq!(got, wanted);
+        }
+        let default = Relationship::new("test", "req", RelationshipType::Verifies);
+        assert_eq!(default.connector, Connector::Arrow);
+    }
+
+    #[test]
+    fn describe_satisfies_in_both_directions() {
+        // arrange
+        let relationship = Relationship::new("login_test", "login", RelationshipType::Satisfies);
+        // act
+        let forward = relationship.describe();
+        let reverse = relationship.describe_reverse();
+        // assert
+        assert_eq!(forward, "login_test satisfies login");
+        assert_eq!(reverse, "login is satisfied by login_test");
+    }
+
+    #[test]
+    fn try_new_with_different_source_and_target() {
+        // act
+        let got = Relationship::try_new(SOURCE, TARGET, KIND).map(|r| r.target);
+        // assert
+        assert_eq!(got, Ok(TARGET.to_string()));
+    }
+
+    #[test]
+    fn try_new_self_relationship_should_error() {
+        // act
+        let got = Relationship::try_new(SOURCE, SOURCE, KIND).map(|r| r.target);
+        // assert
+        assert_eq!(got, Err(MormaidError::SelfRelationship(SOURCE.to_string())));
+    }
+
     #[test]
     fn display_relationship() {
         // arrange
@@ -79,4 +285,53 @@ mod tests {
         // assert
         assert_eq!(got, wanted);
     }
+
+    #[test]
+    fn display_relationship_with_note() {
+        // arrange
+        let wanted = "Foo - contains -> Bar\n%% Bar is a sub-requirement of Foo";
+        // act
+        let got = Relationship::new(SOURCE, TARGET, KIND)
+            .with_note("Bar is a sub-requirement of Foo")
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    // An exhaustive match, so a new relationship type has to be listed here too
+    fn position(kind: &RelationshipType) -> usize {
+        match kind {
+            RelationshipType::Contains => 0,
+            RelationshipType::Copies => 1,
+            RelationshipType::Derives => 2,
+            RelationshipType::Satisfies => 3,
+            RelationshipType::Verifies => 4,
+            RelationshipType::Refines => 5,
+            RelationshipType::Traces => 6,
+        }
+    }
+
+    #[test]
+    fn display_relationship_with_multi_line_note() {
+        // arrange
+        let wanted = "Foo - contains -> Bar\n%% first line\n%% second line";
+        // act
+        let got = Relationship::new(SOURCE, TARGET, KIND)
+            .with_note("first line\nsecond line")
+            .to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn all_variants_lists_every_variant() {
+        // act
+        let got: Vec<usize> = RelationshipType::all_variants()
+            .iter()
+            .map(position)
+            .collect();
+        // assert
+        assert_eq!(RelationshipType::all_variants().len(), 7);
+        assert_eq!(got, [0, 1, 2, 3, 4, 5, 6]);
+    }
 }
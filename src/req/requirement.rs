@@ -1,10 +1,16 @@
 use std::fmt;
 
+use crate::render::RenderOptions;
+use crate::utils::escape_quoted;
+use crate::MormaidError;
+
 // ==================================================================
 // Enums
 // ==================================================================
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum RequirementType {
     Default,
     Functional,
@@ -14,6 +20,21 @@ pub enum RequirementType {
     DesignConstraint,
 }
 
+impl RequirementType {
+    /// Every known variant, in declaration order.
+    #[must_use]
+    pub fn all_variants() -> &'static [Self] {
+        &[
+            RequirementType::Default,
+            RequirementType::Functional,
+            RequirementType::Interface,
+            RequirementType::Performance,
+            RequirementType::Physical,
+            RequirementType::DesignConstraint,
+        ]
+    }
+}
+
 impl fmt::Display for RequirementType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let type_str = match self {
@@ -28,13 +49,43 @@ impl fmt::Display for RequirementType {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Parse a requirement type from its mermaid keyword, e.g. `functionalRequirement`.
+impl TryFrom<&str> for RequirementType {
+    type Error = MormaidError;
+
+    fn try_from(keyword: &str) -> Result<Self, Self::Error> {
+        match keyword {
+            "requirement" => Ok(RequirementType::Default),
+            "functionalRequirement" => Ok(RequirementType::Functional),
+            "interfaceRequirement" => Ok(RequirementType::Interface),
+            "performanceRequirement" => Ok(RequirementType::Performance),
+            "physicalRequirement" => Ok(RequirementType::Physical),
+            "designConstraint" => Ok(RequirementType::DesignConstraint),
+            _ => Err(MormaidError::UnknownVariant {
+                kind: "requirement type",
+                value: keyword.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Risk {
     Low,
     Medium,
     High,
 }
 
+impl Risk {
+    /// Every known variant, in declaration order.
+    #[must_use]
+    pub fn all_variants() -> &'static [Self] {
+        &[Risk::Low, Risk::Medium, Risk::High]
+    }
+}
+
 impl fmt::Display for Risk {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let risk_str = match self {
@@ -46,7 +97,26 @@ impl fmt::Display for Risk {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Parse a risk from its mermaid value, e.g. `High`.
+impl TryFrom<&str> for Risk {
+    type Error = MormaidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "High" => Ok(Risk::High),
+            "Medium" => Ok(Risk::Medium),
+            "Low" => Ok(Risk::Low),
+            _ => Err(MormaidError::UnknownVariant {
+                kind: "risk",
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum VerifyMethod {
     Analysis,
     Inspection,
@@ -54,6 +124,19 @@ pub enum VerifyMethod {
     Demo,
 }
 
+impl VerifyMethod {
+    /// Every known variant, in declaration order.
+    #[must_use]
+    pub fn all_variants() -> &'static [Self] {
+        &[
+            VerifyMethod::Analysis,
+            VerifyMethod::Inspection,
+            VerifyMethod::Test,
+            VerifyMethod::Demo,
+        ]
+    }
+}
+
 impl fmt::Display for VerifyMethod {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let method_str = match self {
@@ -66,11 +149,42 @@ impl fmt::Display for VerifyMethod {
     }
 }
 
+/// Parse a verify method from its mermaid value, e.g. `Demonstration`.
+///
+/// [`VerifyMethod::Demo`] is rendered as `Demonstration`, the value mermaid
+/// expects, so both `Demonstration` and the variant's own name `Demo` are
+/// parsed as [`VerifyMethod::Demo`].
+impl TryFrom<&str> for VerifyMethod {
+    type Error = MormaidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "Analysis" => Ok(VerifyMethod::Analysis),
+            "Inspection" => Ok(VerifyMethod::Inspection),
+            "Test" => Ok(VerifyMethod::Test),
+            "Demo" | "Demonstration" => Ok(VerifyMethod::Demo),
+            _ => Err(MormaidError::UnknownVariant {
+                kind: "verify method",
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl std::str::FromStr for VerifyMethod {
+    type Err = MormaidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        VerifyMethod::try_from(value)
+    }
+}
+
 // ==================================================================
 // Requirement struct and implementation
 // ==================================================================
 
 #[must_use]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Requirement {
     pub kind: RequirementType,
     pub name: String,
@@ -78,6 +192,17 @@ pub struct Requirement {
     pub text: Option<String>,
     pub risk: Option<Risk>,
     pub verify_method: Option<VerifyMethod>,
+    /// The key of the requirement in an external system, e.g. a Jira issue.
+    ///
+    /// The key is preserved when the requirement is serialized, but it's never
+    /// rendered in the diagram.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub external_id: Option<String>,
+    /// Inline CSS rendered as a `style <name> <css>` line, e.g. `fill:#f9f,stroke:#333`.
+    ///
+    /// See [`Requirement::with_style()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: Option<String>,
 }
 
 impl Requirement {
@@ -89,9 +214,40 @@ impl Requirement {
             text: None,
             risk: None,
             verify_method: None,
+            external_id: None,
+            style: None,
         }
     }
 
+    /// Create a new requirement after checking that mermaid can render its name and id.
+    ///
+    /// The name must be a single token of letters, digits, `-`, or `_`, and the id
+    /// must only contain letters, digits, `.`, `-`, or `_`. Use [`Requirement::new()`]
+    /// to skip these checks.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::InvalidName`] or [`MormaidError::InvalidId`] if
+    /// either the name or the id is empty or contains other characters.
+    pub fn try_new(kind: RequirementType, name: &str, id: &str) -> Result<Self, MormaidError> {
+        if !is_valid_token(name, &['-', '_']) {
+            return Err(MormaidError::InvalidName(name.to_string()));
+        }
+        if !is_valid_token(id, &['.', '-', '_']) {
+            return Err(MormaidError::InvalidId(id.to_string()));
+        }
+        Ok(Requirement::new(kind, name, id))
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.set_id(id);
+        self
+    }
+
+    /// Change the id of an existing requirement, e.g. when renumbering requirements.
+    pub fn set_id(&mut self, id: &str) {
+        self.id = id.to_string();
+    }
+
     pub fn with_text(mut self, text: &str) -> Self {
         self.text = Some(text.to_string());
         self
@@ -102,33 +258,94 @@ impl Requirement {
         self
     }
 
+    pub fn with_external_id(mut self, external_id: &str) -> Self {
+        self.external_id = Some(external_id.to_string());
+        self
+    }
+
+    /// Chain with `Requirement::new()` to color or highlight the requirement with inline CSS.
+    ///
+    /// The style is rendered as a `style <name> <css>` line in the diagram's
+    /// styling section, after the diagram body and any risk classes, so it takes
+    /// precedence over [`super::RequirementDiagram::with_risk_styling()`].
+    pub fn with_style(mut self, css: &str) -> Self {
+        self.style = Some(css.to_string());
+        self
+    }
+
     pub fn with_verify_method(mut self, method: VerifyMethod) -> Self {
         self.verify_method = Some(method);
         self
     }
 }
 
-impl fmt::Display for Requirement {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Requirement {
+    /// The key-value pairs of the fields rendered inside the requirement's
+    /// brackets, in the order they're rendered with the default options.
+    ///
+    /// The `id` is always included, followed by the `risk`, `text`, and
+    /// `verifymethod` if they're set. Each value is formatted as it's rendered,
+    /// so the `text` is quoted and escaped, e.g. `("text", "\"Users can log in\"")`.
+    #[must_use]
+    pub fn field_lines(&self) -> Vec<(String, String)> {
+        self.field_lines_with(&RenderOptions::default())
+    }
+
+    // List the rendered fields, applying the options that affect them
+    fn field_lines_with(&self, options: &RenderOptions) -> Vec<(String, String)> {
+        let mut fields = vec![("id".to_string(), self.id.clone())];
+        // include the risk (if populated) or mermaid's implicit default when the options ask for it
+        let risk_field = match &self.risk {
+            Some(risk) => Some(("risk".to_string(), risk.to_string())),
+            None if options.explicit_default_risk => {
+                Some(("risk".to_string(), Risk::Low.to_string()))
+            }
+            None => None,
+        };
+        // include the text (if populated) as a quoted string
+        let text_field = self.text.as_deref().map(|text| {
+            let value = format!("\"{}\"", escape_quoted(text, options.escape_style));
+            ("text".to_string(), value)
+        });
+        // list the text first if the options ask for mermaid's conventional order
+        let optional = if options.text_before_risk {
+            [text_field, risk_field]
+        } else {
+            [risk_field, text_field]
+        };
+        fields.extend(optional.into_iter().flatten());
+        // include the verify method (if populated)
+        if let Some(method) = &self.verify_method {
+            fields.push(("verifymethod".to_string(), method.to_string()));
+        }
+        fields
+    }
+
+    // Render the requirement, applying the options that affect its fields
+    pub(crate) fn render(&self, options: &RenderOptions) -> String {
         // format type and name with an open bracket
         let mut out_str = format!("{} {} {{", self.kind, self.name);
-        // format the id value
-        out_str += &format!("\n    id: {}", self.id);
-        // format the risk value (if populated) on a new indented line
-        if let Some(risk) = &self.risk {
-            out_str += &format!("\n    risk: {risk}");
-        }
-        // format the text value (if populated) on a new indented line
-        if let Some(text) = self.text.as_deref() {
-            out_str += &format!("\n    text: \"{text}\"");
-        }
-        // format the verify method (if populated) on a new indented line
-        if let Some(method) = &self.verify_method {
-            out_str += &format!("\n    verifymethod: {method}");
+        // format each field on a new indented line
+        for (key, value) in self.field_lines_with(options) {
+            out_str += &format!("\n    {key}: {value}");
         }
         // append a final closing bracket on its own line
         out_str += "\n}";
-        write!(f, "{out_str}")
+        out_str
+    }
+}
+
+// Check that the text is non-empty and only has alphanumeric or the allowed characters
+fn is_valid_token(text: &str, allowed: &[char]) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || allowed.contains(&c))
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(&RenderOptions::default()))
     }
 }
 
@@ -164,6 +381,76 @@ mod tests {
         assert_eq!(got.id, ID);
     }
 
+    #[test]
+    fn external_id_isnt_rendered() {
+        // arrange
+        let req = Requirement::new(KIND, NAME, ID).with_external_id("MORM-42");
+        // act
+        let got = req.to_string();
+        // assert
+        assert_eq!(req.external_id.as_deref(), Some("MORM-42"));
+        assert!(!got.contains("MORM-42"));
+        assert_eq!(got, Requirement::new(KIND, NAME, ID).to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn external_id_round_trips_with_serde() {
+        // arrange
+        let req = Requirement::new(KIND, NAME, ID).with_external_id("MORM-42");
+        // act
+        let json = serde_json::to_string(&req).expect("Expected requirement to serialize");
+        let got: Requirement = serde_json::from_str(&json).expect("Expected JSON to parse");
+        // assert
+        assert_eq!(got.external_id.as_deref(), Some("MORM-42"));
+        assert_eq!(got.to_string(), req.to_string());
+    }
+
+    #[test]
+    fn demo_verify_method_round_trips() {
+        // act
+        let rendered = VerifyMethod::Demo.to_string();
+        // assert
+        assert_eq!(rendered, "Demonstration");
+        assert_eq!(rendered.parse::<VerifyMethod>(), Ok(VerifyMethod::Demo));
+        assert_eq!("Demo".parse::<VerifyMethod>(), Ok(VerifyMethod::Demo));
+        assert_eq!(
+            "Demos".parse::<VerifyMethod>(),
+            Err(MormaidError::UnknownVariant {
+                kind: "verify method",
+                value: "Demos".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn try_new_with_valid_name_and_id() {
+        // act
+        let got =
+            Requirement::try_new(KIND, "test_req-1", ID).expect("Expected a valid requirement");
+        // assert
+        assert_eq!(got.name, "test_req-1");
+        assert_eq!(got.id, ID);
+    }
+
+    #[test]
+    fn try_new_with_spaces_in_name_should_error() {
+        // act
+        let got = Requirement::try_new(KIND, "test req", ID).map(|req| req.name);
+        // assert
+        assert_eq!(got, Err(MormaidError::InvalidName("test req".to_string())));
+    }
+
+    #[test]
+    fn try_new_with_malformed_id_should_error() {
+        // act
+        let empty = Requirement::try_new(KIND, NAME, "").map(|req| req.id);
+        let spaced = Requirement::try_new(KIND, NAME, "1 .1").map(|req| req.id);
+        // assert
+        assert_eq!(empty, Err(MormaidError::InvalidId(String::new())));
+        assert_eq!(spaced, Err(MormaidError::InvalidId("1 .1".to_string())));
+    }
+
     #[test]
     fn create_requirement_with_risk() {
         // arrange
@@ -194,6 +481,19 @@ mod tests {
         assert_eq!(got.verify_method, Some(METHOD_WANTED));
     }
 
+    #[test]
+    fn change_requirement_id_after_creation() {
+        // arrange
+        let mut req = Requirement::new(KIND, NAME, ID).with_id("2.1");
+        assert_eq!(req.id, "2.1");
+        // act
+        req.set_id("3.1.4");
+        // assert
+        let got = req.to_string();
+        assert!(got.contains("    id: 3.1.4\n"));
+        assert!(!got.contains(ID));
+    }
+
     #[test]
     fn display_element_with_required_fields_only() {
         // arrange
@@ -224,4 +524,156 @@ mod tests {
         // assert
         assert_eq!(got, wanted);
     }
+
+    #[test]
+    fn display_without_risk_omits_risk_line() {
+        // arrange
+        let req = Requirement::new(KIND, NAME, ID);
+        // act
+        let got = req.render(&RenderOptions::default());
+        // assert
+        assert!(!got.contains("risk:"));
+    }
+
+    #[test]
+    fn explicit_default_risk_emits_low_for_missing_risk() {
+        // arrange
+        let req = Requirement::new(KIND, NAME, ID);
+        let options = RenderOptions::new().with_explicit_default_risk(true);
+        let wanted = format!("requirement {NAME} {{\n    id: {ID}\n    risk: Low\n}}");
+        // act
+        let got = req.render(&options);
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn explicit_default_risk_keeps_set_risk() {
+        // arrange
+        let req = Requirement::new(KIND, NAME, ID).with_risk(Risk::High);
+        let options = RenderOptions::new().with_explicit_default_risk(true);
+        // act
+        let got = req.render(&options);
+        // assert
+        assert!(got.contains("risk: High"));
+        assert!(!got.contains("risk: Low"));
+    }
+
+    #[test]
+    fn display_risk_before_text_by_default() {
+        // arrange
+        let req = Requirement::new(KIND, NAME, ID)
+            .with_text("foo")
+            .with_risk(Risk::High)
+            .with_verify_method(VerifyMethod::Test);
+        let wanted = concat!(
+            "requirement milestone {\n",
+            "    id: 1.1.1\n",
+            "    risk: High\n",
+            "    text: \"foo\"\n",
+            "    verifymethod: Test\n",
+            "}",
+        );
+        // act
+        let got = req.to_string();
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_text_before_risk_when_requested() {
+        // arrange
+        let req = Requirement::new(KIND, NAME, ID)
+            .with_text("foo")
+            .with_risk(Risk::High)
+            .with_verify_method(VerifyMethod::Test);
+        let options = RenderOptions::new().with_text_before_risk(true);
+        let wanted = concat!(
+            "requirement milestone {\n",
+            "    id: 1.1.1\n",
+            "    text: \"foo\"\n",
+            "    risk: High\n",
+            "    verifymethod: Test\n",
+            "}",
+        );
+        // act
+        let got = req.render(&options);
+        // assert
+        assert_eq!(got, wanted);
+    }
+
+    // The position of each variant, with one arm per variant so that adding a
+    // variant won't compile until it's also added here and to `all_variants()`
+    fn requirement_type_position(kind: &RequirementType) -> usize {
+        match kind {
+            RequirementType::Default => 0,
+            RequirementType::Functional => 1,
+            RequirementType::Interface => 2,
+            RequirementType::Performance => 3,
+            RequirementType::Physical => 4,
+            RequirementType::DesignConstraint => 5,
+        }
+    }
+
+    fn risk_position(risk: &Risk) -> usize {
+        match risk {
+            Risk::Low => 0,
+            Risk::Medium => 1,
+            Risk::High => 2,
+        }
+    }
+
+    fn verify_method_position(method: &VerifyMethod) -> usize {
+        match method {
+            VerifyMethod::Analysis => 0,
+            VerifyMethod::Inspection => 1,
+            VerifyMethod::Test => 2,
+            VerifyMethod::Demo => 3,
+        }
+    }
+
+    #[test]
+    fn all_variants_lists_every_variant() {
+        // act
+        let kinds: Vec<usize> = RequirementType::all_variants()
+            .iter()
+            .map(requirement_type_position)
+            .collect();
+        let risks: Vec<usize> = Risk::all_variants().iter().map(risk_position).collect();
+        let methods: Vec<usize> = VerifyMethod::all_variants()
+            .iter()
+            .map(verify_method_position)
+            .collect();
+        // assert
+        assert_eq!(RequirementType::all_variants().len(), 6);
+        assert_eq!(kinds, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(Risk::all_variants().len(), 3);
+        assert_eq!(risks, [0, 1, 2]);
+        assert_eq!(VerifyMethod::all_variants().len(), 4);
+        assert_eq!(methods, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn field_lines_of_full_and_minimal_requirements() {
+        // arrange
+        let full = Requirement::new(RequirementType::Functional, "login", "1.1")
+            .with_risk(Risk::High)
+            .with_text("Users can log in")
+            .with_verify_method(VerifyMethod::Test);
+        let minimal = Requirement::new(RequirementType::Functional, "logout", "1.2");
+        let pair = |key: &str, value: &str| (key.to_string(), value.to_string());
+        // act
+        let got = (full.field_lines(), minimal.field_lines());
+        // assert
+        assert_eq!(
+            got.0,
+            vec![
+                pair("id", "1.1"),
+                pair("risk", "High"),
+                pair("text", "\"Users can log in\""),
+                pair("verifymethod", "Test"),
+            ]
+        );
+        assert_eq!(got.1, vec![pair("id", "1.2")]);
+    }
 }
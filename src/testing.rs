@@ -0,0 +1,137 @@
+//! Helpers for asserting the mermaid output of a diagram.
+
+/// Assert that a diagram (or any other `Display` value) renders to the expected text.
+///
+/// On a mismatch the panic message contains a line-by-line diff, where lines
+/// prefixed with `-` are missing from the rendered output and lines prefixed
+/// with `+` were rendered but not expected.
+///
+/// # Example
+///
+/// ```
+/// # use mormaid::assert_mermaid_eq;
+/// # use mormaid::erd::{Entity, ERD};
+///
+/// let erd = ERD::new().with_entity(Entity::new("ALBUM"));
+/// assert_mermaid_eq!(
+///     erd,
+///     concat!(
+///         "erDiagram\n",
+///         "    %% Entities start\n",
+///         "    ALBUM\n",
+///         "    %% Entities end",
+///     )
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_mermaid_eq {
+    ($got:expr, $wanted:expr $(,)?) => {
+        if let Some(diff) = $crate::testing::diff_lines(&$got.to_string(), &$wanted.to_string()) {
+            panic!("rendered mermaid doesn't match the expected output:\n{diff}");
+        }
+    };
+}
+
+/// Compare two multiline strings and describe the differences between them.
+///
+/// Returns `None` if the strings are equal, otherwise every line of the two
+/// strings prefixed with `-` (only in `wanted`), `+` (only in `got`), or
+/// two spaces (in both).
+#[must_use]
+pub fn diff_lines(got: &str, wanted: &str) -> Option<String> {
+    if got == wanted {
+        return None;
+    }
+    let got: Vec<&str> = got.lines().collect();
+    let wanted: Vec<&str> = wanted.lines().collect();
+    // find the length of the longest common subsequence starting at each pair of lines
+    let mut lengths = vec![vec![0; got.len() + 1]; wanted.len() + 1];
+    for w in (0..wanted.len()).rev() {
+        for g in (0..got.len()).rev() {
+            lengths[w][g] = if wanted[w] == got[g] {
+                lengths[w + 1][g + 1] + 1
+            } else {
+                lengths[w + 1][g].max(lengths[w][g + 1])
+            };
+        }
+    }
+    // walk the table to mark each line as shared, missing, or unexpected
+    let mut out_lines = Vec::new();
+    let (mut w, mut g) = (0, 0);
+    while w < wanted.len() || g < got.len() {
+        if w < wanted.len() && g < got.len() && wanted[w] == got[g] {
+            out_lines.push(format!("  {}", wanted[w]));
+            w += 1;
+            g += 1;
+        } else if w < wanted.len() && (g == got.len() || lengths[w + 1][g] >= lengths[w][g + 1]) {
+            out_lines.push(format!("- {}", wanted[w]));
+            w += 1;
+        } else {
+            out_lines.push(format!("+ {}", got[g]));
+            g += 1;
+        }
+    }
+    // strings that only differ by a trailing newline have identical lines
+    if out_lines.iter().all(|line| line.starts_with("  ")) {
+        out_lines.push("(the outputs differ in trailing whitespace)".to_string());
+    }
+    Some(out_lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_of_equal_strings_is_none() {
+        // act
+        let got = diff_lines("erDiagram\n    ALBUM", "erDiagram\n    ALBUM");
+        // assert
+        assert_eq!(got, None);
+    }
+
+    #[test]
+    fn diff_marks_missing_and_unexpected_lines() {
+        // arrange
+        let wanted = concat!(
+            "  erDiagram\n",
+            "-     ALBUM\n",
+            "+     SONG\n",
+            "      ARTIST"
+        );
+        // act
+        let got = diff_lines(
+            "erDiagram\n    SONG\n    ARTIST",
+            "erDiagram\n    ALBUM\n    ARTIST",
+        );
+        // assert
+        assert_eq!(got.as_deref(), Some(wanted));
+    }
+
+    #[test]
+    fn diff_marks_inserted_lines_without_marking_later_lines() {
+        // arrange
+        let wanted = concat!("  erDiagram\n", "+     ARTIST\n", "      ALBUM");
+        // act
+        let got = diff_lines("erDiagram\n    ARTIST\n    ALBUM", "erDiagram\n    ALBUM");
+        // assert
+        assert_eq!(got.as_deref(), Some(wanted));
+    }
+
+    #[test]
+    fn assert_mermaid_eq_passes_for_matching_output() {
+        // arrange
+        let erd = crate::erd::ERD::new();
+        // assert
+        crate::assert_mermaid_eq!(erd, "erDiagram");
+    }
+
+    #[test]
+    #[should_panic = "-     ALBUM"]
+    fn assert_mermaid_eq_panics_with_diff_for_mismatched_output() {
+        // arrange
+        let erd = crate::erd::ERD::new();
+        // assert
+        crate::assert_mermaid_eq!(erd, "erDiagram\n    ALBUM");
+    }
+}
@@ -1,6 +1,10 @@
+//! Helpers shared by the diagrams, such as sanitizing identifiers.
+
 use std::fmt;
 
-pub trait Indent {
+use crate::render::{EscapeStyle, RenderOptions};
+
+pub(crate) trait Indent {
     fn indent(&self, size: usize) -> String;
 }
 
@@ -20,15 +24,161 @@ impl Indent for String {
     }
 }
 
-pub fn append_items<T, I>(mut curr_str: String, items: T, note: &str, indent: usize) -> String
+pub(crate) fn append_items<T, I>(curr_str: String, items: T, note: &str, indent: usize) -> String
+where
+    T: IntoIterator<Item = I>,
+    I: fmt::Display,
+{
+    append_items_with(curr_str, items, note, indent, &RenderOptions::default())
+}
+
+pub(crate) fn append_items_with<T, I>(
+    mut curr_str: String,
+    items: T,
+    note: &str,
+    indent: usize,
+    options: &RenderOptions,
+) -> String
 where
     T: IntoIterator<Item = I>,
     I: fmt::Display,
 {
-    curr_str += &format!("\n{}%% {} start", " ".repeat(indent), note);
+    if !options.compact {
+        curr_str += &format!("\n{}%% {} start", " ".repeat(indent), note);
+    }
     for item in items {
         curr_str += &format!("\n{}", &item.to_string().indent(indent));
     }
-    curr_str += &format!("\n{}%% {} end", " ".repeat(indent), note);
+    if !options.compact {
+        curr_str += &format!("\n{}%% {} end", " ".repeat(indent), note);
+    }
+    curr_str
+}
+
+// Append mermaid's accessibility directives after the diagram keyword, using the
+// block form of `accDescr` for a description that spans several lines
+pub(crate) fn append_accessibility(
+    mut curr_str: String,
+    title: Option<&str>,
+    descr: Option<&str>,
+) -> String {
+    if let Some(title) = title {
+        curr_str += &format!("\n    accTitle: {title}");
+    }
+    match descr {
+        Some(descr) if descr.contains('\n') => {
+            curr_str += "\n    accDescr {";
+            for line in descr.lines() {
+                curr_str += &format!("\n        {line}");
+            }
+            curr_str += "\n    }";
+        }
+        Some(descr) => curr_str += &format!("\n    accDescr: {descr}"),
+        None => {}
+    }
     curr_str
 }
+
+// Escape the characters that have a special meaning in HTML text
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// Escape the characters that would end or corrupt a quoted mermaid string.
+// The escape character of each style is escaped first so that a literal one
+// can't be mistaken for the start of one of the escapes produced after it.
+pub(crate) fn escape_quoted(text: &str, style: EscapeStyle) -> String {
+    match style {
+        EscapeStyle::Entity => text.replace('#', "#35;").replace('"', "#quot;"),
+        EscapeStyle::Html => text.replace('&', "&amp;").replace('"', "&quot;"),
+        EscapeStyle::Backslash => text.replace('\\', "\\\\").replace('"', "\\\""),
+    }
+}
+
+// Reverse `escape_quoted` with the entity style, decoding the entity codes in the opposite order
+pub(crate) fn unescape_quoted(text: &str) -> String {
+    text.replace("#quot;", "\"").replace("#35;", "#")
+}
+
+/// Convert text into an identifier that mermaid can render without quotes.
+///
+/// Each run of whitespace becomes a single `_`, leading and trailing whitespace
+/// is trimmed, and punctuation other than `-` and `_` is dropped, so the result
+/// only has letters, digits, `-`, and `_`. It's empty if the input has none of those.
+///
+/// ```
+/// use mormaid::utils::sanitize_identifier;
+///
+/// assert_eq!(sanitize_identifier("Music Genre (v2)"), "Music_Genre_v2");
+/// ```
+#[must_use]
+pub fn sanitize_identifier(input: &str) -> String {
+    input
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<String>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_messy_identifiers() {
+        // arrange
+        let cases = [
+            ("album", "album"),
+            ("MUSIC GENRE", "MUSIC_GENRE"),
+            ("  leading and   trailing  ", "leading_and_trailing"),
+            ("user's e-mail!", "users_e-mail"),
+            ("order.line_item", "orderline_item"),
+            ("tab\tand\nnewline", "tab_and_newline"),
+            ("caf\u{e9} menu", "caf\u{e9}_menu"),
+            ("a & b", "a_b"),
+            ("?!", ""),
+        ];
+        for (input, wanted) in cases {
+            // act
+            let got = sanitize_identifier(input);
+            // assert
+            assert_eq!(got, wanted, "sanitizing {input:?}");
+        }
+    }
+
+    #[test]
+    fn escape_quote_with_each_style() {
+        // arrange
+        let text = r#"say "hi" & \ #1"#;
+        let styles = [
+            (EscapeStyle::Entity, r"say #quot;hi#quot; & \ #35;1"),
+            (EscapeStyle::Html, r"say &quot;hi&quot; &amp; \ #1"),
+            (EscapeStyle::Backslash, r#"say \"hi\" & \\ #1"#),
+        ];
+        for (style, wanted) in styles {
+            // act
+            let got = escape_quoted(text, style);
+            // assert
+            assert_eq!(got, wanted);
+        }
+    }
+
+    #[test]
+    fn escape_quoted_round_trips_hash_and_quote() {
+        // arrange
+        let text = r#"track #1 is "intro" not #quot;"#;
+        let wanted = "track #35;1 is #quot;intro#quot; not #35;quot;";
+        // act
+        let escaped = escape_quoted(text, EscapeStyle::Entity);
+        // assert
+        assert_eq!(escaped, wanted);
+        assert_eq!(unescape_quoted(&escaped), text);
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+/// Represents the x-axis of an XY chart, which is either a list of
+/// categories or a numeric range.
+#[derive(Debug, PartialEq)]
+pub enum XAxis {
+    Categories {
+        title: Option<String>,
+        categories: Vec<String>,
+    },
+    Range {
+        title: Option<String>,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl XAxis {
+    /// Create an x-axis with one category per data point.
+    #[must_use]
+    pub fn categories(categories: &[&str]) -> Self {
+        XAxis::Categories {
+            title: None,
+            categories: categories.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Create an x-axis that spans a numeric range.
+    #[must_use]
+    pub fn range(min: f64, max: f64) -> Self {
+        XAxis::Range {
+            title: None,
+            min,
+            max,
+        }
+    }
+
+    #[must_use]
+    pub fn with_title(mut self, axis_title: &str) -> Self {
+        match &mut self {
+            XAxis::Categories { title, .. } | XAxis::Range { title, .. } => {
+                *title = Some(axis_title.to_string());
+            }
+        }
+        self
+    }
+
+    /// The number of categories on the axis, or `None` for a numeric range.
+    #[must_use]
+    pub fn category_count(&self) -> Option<usize> {
+        match self {
+            XAxis::Categories { categories, .. } => Some(categories.len()),
+            XAxis::Range { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for XAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out_str = "x-axis".to_string();
+        match self {
+            XAxis::Categories { title, categories } => {
+                if let Some(title) = title.as_deref() {
+                    out_str += &format!(" \"{title}\"");
+                }
+                let categories: Vec<String> =
+                    categories.iter().map(|c| format!("\"{c}\"")).collect();
+                out_str += &format!(" [{}]", categories.join(", "));
+            }
+            XAxis::Range { title, min, max } => {
+                if let Some(title) = title.as_deref() {
+                    out_str += &format!(" \"{title}\"");
+                }
+                out_str += &format!(" {min} --> {max}");
+            }
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+/// Represents the y-axis of an XY chart.
+///
+/// If no range is set, mermaid derives the range from the chart's data.
+#[must_use]
+#[derive(Debug, PartialEq, Default)]
+pub struct YAxis {
+    pub title: Option<String>,
+    pub range: Option<(f64, f64)>,
+}
+
+impl YAxis {
+    pub fn new() -> Self {
+        YAxis {
+            title: None,
+            range: None,
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+}
+
+impl fmt::Display for YAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out_str = "y-axis".to_string();
+        if let Some(title) = self.title.as_deref() {
+            out_str += &format!(" \"{title}\"");
+        }
+        if let Some((min, max)) = self.range {
+            out_str += &format!(" {min} --> {max}");
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_category_axis() {
+        // act
+        let got = XAxis::categories(&["jan", "feb"]).to_string();
+        // assert
+        assert_eq!(got, "x-axis [\"jan\", \"feb\"]");
+    }
+
+    #[test]
+    fn display_range_axis_with_title() {
+        // act
+        let got = XAxis::range(0.0, 100.0).with_title("Time").to_string();
+        // assert
+        assert_eq!(got, "x-axis \"Time\" 0 --> 100");
+    }
+
+    #[test]
+    fn display_y_axis_with_title_and_range() {
+        // act
+        let got = YAxis::new()
+            .with_title("Revenue")
+            .with_range(4000.0, 11000.0)
+            .to_string();
+        // assert
+        assert_eq!(got, "y-axis \"Revenue\" 4000 --> 11000");
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+
+pub mod axis;
+pub mod series;
+
+use crate::MormaidError;
+pub use axis::{XAxis, YAxis};
+pub use series::{Series, SeriesKind};
+
+// ================================================================
+// XYChart struct and implementation
+// ================================================================
+
+/// Represents a mermaid `xychart-beta` diagram.
+///
+/// # Example
+///
+/// ```
+/// # use crate::mormaid::xychart::{Series, XAxis, XYChart, YAxis};
+///
+/// let mut chart = XYChart::new()
+///     .with_title("Sales Revenue")
+///     .with_x_axis(XAxis::categories(&["jan", "feb", "mar"]))
+///     .with_y_axis(YAxis::new().with_title("Revenue (in $)"));
+/// chart.try_add_series(Series::bar(vec![5000.0, 6000.0, 7500.0])).unwrap();
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct XYChart {
+    pub title: Option<String>,
+    pub x_axis: Option<XAxis>,
+    pub y_axis: Option<YAxis>,
+    pub series: Vec<Series>,
+}
+
+impl XYChart {
+    pub fn new() -> Self {
+        XYChart {
+            title: None,
+            x_axis: None,
+            y_axis: None,
+            series: Vec::new(),
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Set the x-axis without checking the series that were already added,
+    /// use [`XYChart::try_with_x_axis()`] to check them against its categories.
+    pub fn with_x_axis(mut self, axis: XAxis) -> Self {
+        self.x_axis = Some(axis);
+        self
+    }
+
+    /// Set the x-axis, checking that each existing series has one value per category.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::SeriesLengthMismatch`] for the first series that
+    /// doesn't have exactly one value per category of the new axis.
+    pub fn try_with_x_axis(mut self, axis: XAxis) -> Result<Self, MormaidError> {
+        if let Some(expected) = axis.category_count() {
+            if let Some(series) = self.series.iter().find(|s| s.values.len() != expected) {
+                return Err(MormaidError::SeriesLengthMismatch {
+                    expected,
+                    found: series.values.len(),
+                });
+            }
+        }
+        self.x_axis = Some(axis);
+        Ok(self)
+    }
+
+    pub fn with_y_axis(mut self, axis: YAxis) -> Self {
+        self.y_axis = Some(axis);
+        self
+    }
+}
+
+// implement the Display trait
+impl fmt::Display for XYChart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // initialize the xychart
+        let mut out_str = "xychart-beta".to_string();
+        // append the title and axes if they're populated
+        if let Some(title) = self.title.as_deref() {
+            out_str += &format!("\n    title \"{title}\"");
+        }
+        if let Some(axis) = &self.x_axis {
+            out_str += &format!("\n    {axis}");
+        }
+        if let Some(axis) = &self.y_axis {
+            out_str += &format!("\n    {axis}");
+        }
+        // append each series on its own indented line
+        for series in &self.series {
+            out_str += &format!("\n    {series}");
+        }
+        write!(f, "{out_str}")
+    }
+}
+
+// =============================================
+// Implement XYChart methods to manage series
+// =============================================
+impl XYChart {
+    /// Add a series of data to `XYChart.series`.
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::SeriesLengthMismatch`] if the x-axis has categories
+    /// and the series doesn't have exactly one value per category, in which case
+    /// the series isn't added.
+    pub fn try_add_series(&mut self, series: Series) -> Result<(), MormaidError> {
+        let category_count = self.x_axis.as_ref().and_then(XAxis::category_count);
+        if let Some(expected) = category_count {
+            if series.values.len() != expected {
+                return Err(MormaidError::SeriesLengthMismatch {
+                    expected,
+                    found: series.values.len(),
+                });
+            }
+        }
+        self.series.push(series);
+        Ok(())
+    }
+
+    /// Add a series to the chart on creation by chaining with [`XYChart::new()`].
+    ///
+    /// # Errors
+    /// Returns [`MormaidError::SeriesLengthMismatch`] if the series doesn't have
+    /// exactly one value per x-axis category.
+    pub fn with_series(mut self, series: Series) -> Result<Self, MormaidError> {
+        self.try_add_series(series)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn quarterly_chart() -> XYChart {
+        XYChart::new()
+            .with_title("Sales Revenue")
+            .with_x_axis(XAxis::categories(&["Q1", "Q2", "Q3"]))
+            .with_y_axis(YAxis::new().with_title("Revenue").with_range(0.0, 100.0))
+    }
+
+    #[test]
+    fn display_chart_with_bar_series() {
+        // arrange
+        let wanted = concat!(
+            "xychart-beta\n",
+            "    title \"Sales Revenue\"\n",
+            "    x-axis [\"Q1\", \"Q2\", \"Q3\"]\n",
+            "    y-axis \"Revenue\" 0 --> 100\n",
+            "    bar [50, 60, 75]",
+        );
+        // act
+        let got = quarterly_chart()
+            .with_series(Series::bar(vec![50.0, 60.0, 75.0]))
+            .expect("Expected series to be added")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn display_chart_with_line_series() {
+        // arrange
+        let wanted = concat!(
+            "xychart-beta\n",
+            "    x-axis \"Day\" 1 --> 3\n",
+            "    line [2.5, 3, 1]",
+        );
+        // act
+        let got = XYChart::new()
+            .with_x_axis(XAxis::range(1.0, 3.0).with_title("Day"))
+            .with_series(Series::line(vec![2.5, 3.0, 1.0]))
+            .expect("Expected series to be added")
+            .to_string();
+        // assert
+        crate::assert_mermaid_eq!(got, wanted);
+    }
+
+    #[test]
+    fn add_series_with_wrong_length_should_error() {
+        // arrange
+        let mut chart = quarterly_chart();
+        // act
+        let got = chart.try_add_series(Series::line(vec![50.0, 60.0]));
+        // assert
+        assert_eq!(
+            got,
+            Err(MormaidError::SeriesLengthMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert!(chart.series.is_empty());
+    }
+
+    #[test]
+    fn try_with_x_axis_after_series_of_wrong_length_should_error() {
+        // arrange
+        let chart = XYChart::new()
+            .with_series(Series::bar(vec![50.0, 60.0]))
+            .expect("Expected a chart without an x-axis to accept the series");
+        // act
+        let got = chart
+            .try_with_x_axis(XAxis::categories(&["Q1", "Q2", "Q3"]))
+            .map(|chart| chart.series.len());
+        // assert
+        assert_eq!(
+            got,
+            Err(MormaidError::SeriesLengthMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+/// The way a series of data is drawn on an XY chart.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SeriesKind {
+    Bar,
+    Line,
+}
+
+impl fmt::Display for SeriesKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind_str = match self {
+            SeriesKind::Bar => "bar",
+            SeriesKind::Line => "line",
+        };
+        write!(f, "{kind_str}")
+    }
+}
+
+/// Represents a series of data points plotted on an XY chart.
+#[must_use]
+#[derive(Debug, PartialEq)]
+pub struct Series {
+    pub kind: SeriesKind,
+    pub values: Vec<f64>,
+}
+
+impl Series {
+    pub fn new(kind: SeriesKind, values: Vec<f64>) -> Self {
+        Series { kind, values }
+    }
+
+    pub fn bar(values: Vec<f64>) -> Self {
+        Series::new(SeriesKind::Bar, values)
+    }
+
+    pub fn line(values: Vec<f64>) -> Self {
+        Series::new(SeriesKind::Line, values)
+    }
+}
+
+impl fmt::Display for Series {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self.values.iter().map(ToString::to_string).collect();
+        write!(f, "{} [{}]", self.kind, values.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_bar_series() {
+        // act
+        let got = Series::bar(vec![5000.0, 6000.0, 7500.5]).to_string();
+        // assert
+        assert_eq!(got, "bar [5000, 6000, 7500.5]");
+    }
+
+    #[test]
+    fn display_line_series() {
+        // act
+        let got = Series::line(vec![1.0, 2.0]).to_string();
+        // assert
+        assert_eq!(got, "line [1, 2]");
+    }
+}
@@ -0,0 +1,35 @@
+//! Render an ERD to PNG with the mermaid CLI.
+//!
+//! These tests only run when the `cli` feature is enabled, and they're skipped
+//! when `mmdc` isn't installed.
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+use mormaid::erd::{Cardinality, Relationship, ERD};
+
+// The first bytes of every PNG file
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+fn mmdc_is_installed() -> bool {
+    Command::new("mmdc").arg("--version").output().is_ok()
+}
+
+#[test]
+fn render_erd_to_png() {
+    if !mmdc_is_installed() {
+        eprintln!("skipping render_erd_to_png because mmdc isn't installed");
+        return;
+    }
+    // arrange
+    let erd = ERD::new().with_relationship(Relationship::new(
+        "ALBUM",
+        "SONG",
+        Cardinality::ExactlyOne,
+        Cardinality::OneOrMore,
+    ));
+    // act
+    let png = erd.render_png().expect("Expected mmdc to render the ERD");
+    // assert
+    assert!(png.starts_with(&PNG_SIGNATURE));
+}
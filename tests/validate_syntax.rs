@@ -0,0 +1,30 @@
+//! Validate a diagram's syntax with the mermaid CLI.
+//!
+//! These tests only run when the `cli` feature is enabled, and they're skipped
+//! when `mmdc` isn't installed.
+#![cfg(feature = "cli")]
+
+use mormaid::erd::{Cardinality, Relationship, ERD};
+use mormaid::{Diagram, RenderError};
+
+#[test]
+fn valid_erd_passes_validation() {
+    // arrange
+    let erd = ERD::new().with_relationship(
+        Relationship::new(
+            "ALBUM",
+            "SONG",
+            Cardinality::ExactlyOne,
+            Cardinality::OneOrMore,
+        )
+        .with_label("includes"),
+    );
+    // act
+    let got = erd.validate_syntax();
+    // assert
+    if matches!(got, Err(RenderError::MissingCli)) {
+        eprintln!("skipping valid_erd_passes_validation because mmdc isn't installed");
+        return;
+    }
+    assert!(got.is_ok(), "Expected the ERD to be valid, got {got:?}");
+}